@@ -0,0 +1,109 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+use crate::types::AuditEntry;
+
+/// Append-only JSONL log of engine write operations.
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn append(&self, operation: &str, ids: &[&str]) -> Result<()> {
+        let entry = AuditEntry {
+            timestamp: Utc::now(),
+            operation: operation.to_string(),
+            ids: ids.iter().map(|id| id.to_string()).collect(),
+            pid: std::process::id(),
+            user: current_user(),
+        };
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("failed to open audit log: {}", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)
+            .with_context(|| format!("failed to write audit log: {}", self.path.display()))
+    }
+}
+
+/// Reads every entry from an audit log; a missing file reads as empty.
+pub fn read_audit_log(path: &Path) -> Result<Vec<AuditEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("failed to read audit log: {}", path.display()))?;
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_no, line)| {
+            serde_json::from_str(line).with_context(|| {
+                format!(
+                    "failed to decode audit log line {}: {}",
+                    line_no + 1,
+                    path.display()
+                )
+            })
+        })
+        .collect()
+}
+
+fn current_user() -> Option<String> {
+    ["USER", "USERNAME"]
+        .into_iter()
+        .find_map(|key| std::env::var(key).ok().filter(|value| !value.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    use super::{read_audit_log, AuditLog};
+
+    #[test]
+    fn audit_log_appends_one_entry_per_operation() -> Result<()> {
+        let temp = TempDir::new()?;
+        let path = temp.path().join("audit.log");
+        let log = AuditLog::new(path.clone());
+
+        log.append("remember", &["ep-1"])?;
+        log.append("pin", &["fact-1"])?;
+        log.append("dream", &[])?;
+
+        assert!(!std::fs::read_to_string(&path)?
+            .lines()
+            .nth(2)
+            .expect("expected dream entry")
+            .contains("\"ids\""));
+        let entries = read_audit_log(&path)?;
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].operation, "remember");
+        assert_eq!(entries[0].ids, vec!["ep-1".to_string()]);
+        assert_eq!(entries[1].operation, "pin");
+        assert_eq!(entries[1].pid, std::process::id());
+        assert!(entries[2].ids.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn missing_audit_log_reads_as_empty() -> Result<()> {
+        let temp = TempDir::new()?;
+
+        assert!(read_audit_log(&temp.path().join("audit.log"))?.is_empty());
+        Ok(())
+    }
+}
@@ -8,6 +8,20 @@ impl Database {
         init_schema(&conn)?;
         Ok(Self {
             conn: Mutex::new(conn),
+            id_scheme: IdScheme::default(),
         })
     }
+
+    /// Rebuilds the database file to drop free pages, then folds the WAL back in and
+    /// truncates it so the reclaimed space shows up on disk.
+    pub fn vacuum(&self) -> Result<()> {
+        let conn = self.conn.lock().expect("sqlite mutex poisoned");
+        conn.execute_batch("VACUUM; PRAGMA wal_checkpoint(TRUNCATE);")?;
+        Ok(())
+    }
+
+    pub fn with_id_scheme(mut self, id_scheme: IdScheme) -> Self {
+        self.id_scheme = id_scheme;
+        self
+    }
 }
@@ -6,7 +6,6 @@ use std::{
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, OptionalExtension};
-use uuid::Uuid;
 
 mod connection;
 mod index_jobs;
@@ -24,7 +23,7 @@ mod tests;
 
 use crate::types::{
     EdgeRecord, EntityInput, EntityRecord, EpisodeInput, EpisodeRecord, FactInput, FactRecord,
-    IndexStatus, LayerSummary, MemoryLayer, MemoryRecord,
+    IdScheme, IndexStatus, LayerSummary, MemoryLayer, MemoryRecord,
 };
 use index_jobs::{
     clear_index_jobs_by_ids, fail_index_jobs_by_ids, index_job_observability,
@@ -36,10 +35,11 @@ use support::{
     count_table, json_to_vec, memory_key, now_ts, sort_l3_records, table_for_kind, vec_to_json,
 };
 
-pub(crate) use support::normalize_text;
+pub(crate) use support::{normalize_text, stable_episode_id};
 
 pub struct Database {
     conn: Mutex<Connection>,
+    id_scheme: IdScheme,
 }
 
 pub struct ObservationContext<'a> {
@@ -45,6 +45,26 @@ impl Database {
         .optional()
         .map_err(Into::into)
     }
+    pub fn load_active_episodes(&self) -> Result<Vec<EpisodeRecord>> {
+        let conn = self.conn.lock().expect("sqlite mutex poisoned");
+        let mut stmt = conn.prepare(
+            "SELECT id, content, layer, confidence, source_episode_id, session_id, created_at, updated_at, last_seen_at,
+                    archived_at, invalidated_at, hit_count
+             FROM episodes
+             WHERE archived_at IS NULL AND invalidated_at IS NULL
+             ORDER BY created_at ASC",
+        )?;
+        let rows = stmt.query_map([], map_episode)?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+    pub fn mentioned_entity_ids(&self, episode_id: &str) -> Result<Vec<String>> {
+        let conn = self.conn.lock().expect("sqlite mutex poisoned");
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT entity_id FROM mentions WHERE episode_id = ?1 ORDER BY entity_id",
+        )?;
+        let rows = stmt.query_map(params![episode_id], |row| row.get(0))?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
     pub fn load_unstructured_episodes(&self, layers: &[MemoryLayer]) -> Result<Vec<EpisodeRecord>> {
         if layers.is_empty() {
             return Ok(Vec::new());
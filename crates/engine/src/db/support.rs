@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
 use serde_json::Value;
+use uuid::Uuid;
 
 use crate::types::MemoryRecord;
 
@@ -11,6 +12,18 @@ pub(crate) fn normalize_text(text: &str) -> String {
         .join(" ")
 }
 
+/// Fixed namespace for content-derived episode ids; changing it would re-key every stable id.
+const STABLE_EPISODE_NAMESPACE: Uuid = Uuid::from_u128(0x6d656d6f_2d65_7069_736f_646500000001);
+
+/// UUIDv5 of the normalized content, so case and whitespace changes keep the same id.
+pub(crate) fn stable_episode_id(content: &str) -> String {
+    Uuid::new_v5(
+        &STABLE_EPISODE_NAMESPACE,
+        normalize_text(content).as_bytes(),
+    )
+    .to_string()
+}
+
 pub(super) fn now_ts() -> i64 {
     Utc::now().timestamp_millis()
 }
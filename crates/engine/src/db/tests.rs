@@ -1,4 +1,4 @@
-use super::{Database, ObservationContext};
+use super::{stable_episode_id, Database, ObservationContext};
 use anyhow::Result;
 use chrono::{TimeZone, Utc};
 use rusqlite::{Connection, OptionalExtension};
@@ -734,3 +734,15 @@ fn index_status_exposes_failed_attempts_and_latest_error() -> Result<()> {
     assert_eq!(status.last_error.as_deref(), Some("latest vector failure"));
     Ok(())
 }
+
+#[test]
+fn stable_episode_id_ignores_case_and_whitespace() {
+    let id = stable_episode_id("Alice  keeps a\nlaunch checklist.");
+
+    assert_eq!(id, stable_episode_id("alice keeps a launch checklist."));
+    assert_ne!(id, stable_episode_id("Bob keeps a launch checklist."));
+    assert_eq!(
+        uuid::Uuid::parse_str(&id).map(|uuid| uuid.get_version_num()),
+        Ok(5)
+    );
+}
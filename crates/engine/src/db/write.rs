@@ -5,13 +5,20 @@ impl Database {
         &self,
         input: &EpisodeInput,
         vector: Option<&[f32]>,
+    ) -> Result<EpisodeRecord> {
+        self.insert_episode_with_id(&self.id_scheme.new_id(), input, vector)
+    }
+    pub fn insert_episode_with_id(
+        &self,
+        id: &str,
+        input: &EpisodeInput,
+        vector: Option<&[f32]>,
     ) -> Result<EpisodeRecord> {
         let conn = self.conn.lock().expect("sqlite mutex poisoned");
         let now = input
             .recorded_at
             .map(|ts| ts.timestamp_millis())
             .unwrap_or_else(now_ts);
-        let id = Uuid::new_v4().to_string();
         let vector_json = vector.map(vec_to_json).transpose()?;
         conn.execute(
             "INSERT INTO episodes
@@ -35,15 +42,49 @@ impl Database {
              VALUES (?1, 'episode', ?2, 'active', ?3, ?3, ?3)",
             params![id, input.layer.as_str(), now],
         )?;
-        queue_text_index_job(&conn, "episode", &id, IndexJobOperation::Upsert)?;
+        queue_text_index_job(&conn, "episode", id, IndexJobOperation::Upsert)?;
         if vector_json.is_some() {
-            queue_vector_index_job(&conn, "episode", &id, IndexJobOperation::Upsert)?;
+            queue_vector_index_job(&conn, "episode", id, IndexJobOperation::Upsert)?;
         }
 
         drop(conn);
-        self.get_episode(&id)?
+        self.get_episode(id)?
             .context("failed to load inserted episode")
     }
+    /// Finds an active episode whose normalized content matches `content`, oldest first.
+    pub fn find_active_episode_by_content(&self, content: &str) -> Result<Option<EpisodeRecord>> {
+        let episode_id = {
+            let conn = self.conn.lock().expect("sqlite mutex poisoned");
+            conn.query_row(
+                "SELECT id FROM episodes
+                 WHERE archived_at IS NULL
+                   AND invalidated_at IS NULL
+                   AND normalized_content = ?1
+                 ORDER BY created_at ASC
+                 LIMIT 1",
+                params![normalize_text(content)],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?
+        };
+
+        match episode_id {
+            Some(id) => self.get_episode(&id),
+            None => Ok(None),
+        }
+    }
+    pub fn touch_episode(&self, episode_id: &str, observed_at: DateTime<Utc>) -> Result<()> {
+        let conn = self.conn.lock().expect("sqlite mutex poisoned");
+        let observed_at_ts = observed_at.timestamp_millis();
+        conn.execute(
+            "UPDATE episodes
+             SET updated_at = MAX(updated_at, ?2),
+                 last_seen_at = MAX(last_seen_at, ?2)
+             WHERE id = ?1",
+            params![episode_id, observed_at_ts],
+        )?;
+        Ok(())
+    }
     pub fn upsert_entity(
         &self,
         input: &EntityInput,
@@ -77,7 +118,7 @@ impl Database {
             )?;
             existing_id
         } else {
-            let entity_id = Uuid::new_v4().to_string();
+            let entity_id = self.id_scheme.new_id();
             let vector_json = vector.map(vec_to_json).transpose()?;
             conn.execute(
                 "INSERT INTO entities
@@ -108,7 +149,7 @@ impl Database {
                  (id, entity_id, alias, normalized_alias, created_at)
                  VALUES (?1, ?2, ?3, ?4, ?5)",
                 params![
-                    Uuid::new_v4().to_string(),
+                    self.id_scheme.new_id(),
                     entity_id,
                     alias,
                     normalize_text(alias),
@@ -139,7 +180,7 @@ impl Database {
              (id, episode_id, entity_id, role, confidence, created_at)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
             params![
-                Uuid::new_v4().to_string(),
+                self.id_scheme.new_id(),
                 episode_id,
                 entity_id,
                 role,
@@ -169,7 +210,7 @@ impl Database {
                    AND role = ?4
              )",
             params![
-                Uuid::new_v4().to_string(),
+                self.id_scheme.new_id(),
                 episode_id,
                 entity_id,
                 role,
@@ -191,7 +232,7 @@ impl Database {
         let conn = self.conn.lock().expect("sqlite mutex poisoned");
         let observed_at_ts = observation.observed_at.timestamp_millis();
         let vector_json = vector.map(vec_to_json).transpose()?;
-        let id = Uuid::new_v4().to_string();
+        let id = self.id_scheme.new_id();
         conn.execute(
             "INSERT INTO facts
              (id, subject_entity_id, subject_text, predicate, object_entity_id, object_text, confidence, source_episode_id, layer, valid_from, created_at, updated_at, hit_count, vector_json)
@@ -233,7 +274,7 @@ impl Database {
     ) -> Result<EdgeRecord> {
         let conn = self.conn.lock().expect("sqlite mutex poisoned");
         let observed_at_ts = observation.observed_at.timestamp_millis();
-        let id = Uuid::new_v4().to_string();
+        let id = self.id_scheme.new_id();
         conn.execute(
             "INSERT INTO edges
              (id, subject_entity_id, predicate, object_entity_id, weight, source_episode_id, layer, valid_from, created_at, updated_at, hit_count)
@@ -0,0 +1,244 @@
+use anyhow::Result;
+
+use crate::types::{ClusterMember, GraphEdge, GraphNode, MemoryCluster, MemoryGraph, MemoryRecord};
+
+use super::MemoryEngine;
+
+impl MemoryEngine {
+    /// Groups active memories by their stored vectors with spherical k-means.
+    pub fn cluster(&self, k: usize, iterations: usize) -> Result<Vec<MemoryCluster>> {
+        if k == 0 {
+            anyhow::bail!("cluster count must be at least 1");
+        }
+        let documents = self.active_vector_documents()?;
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let vectors = documents
+            .iter()
+            .map(|(_, vector)| normalized(vector))
+            .collect::<Vec<_>>();
+        let (assignments, centroids) = kmeans(&vectors, k.min(vectors.len()), iterations.max(1));
+
+        let mut clusters = centroids
+            .iter()
+            .enumerate()
+            .filter_map(|(cluster, centroid)| {
+                let members = assignments
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, assigned)| **assigned == cluster)
+                    .map(|(index, _)| index)
+                    .collect::<Vec<_>>();
+                let label_index = *members.iter().max_by(|left, right| {
+                    dot(&vectors[**left], centroid).total_cmp(&dot(&vectors[**right], centroid))
+                })?;
+                let label = &documents[label_index].0;
+                Some(MemoryCluster {
+                    label_kind: label.kind().to_string(),
+                    label_id: label.id().to_string(),
+                    label: label.text_for_ranking(),
+                    members: members
+                        .iter()
+                        .map(|index| ClusterMember {
+                            kind: documents[*index].0.kind().to_string(),
+                            id: documents[*index].0.id().to_string(),
+                        })
+                        .collect(),
+                })
+            })
+            .collect::<Vec<_>>();
+        clusters.sort_by(|left, right| right.members.len().cmp(&left.members.len()));
+        Ok(clusters)
+    }
+
+    /// Links every pair of active memories whose stored vectors have cosine similarity at or
+    /// above `threshold`.
+    pub fn similarity_graph(&self, threshold: f32) -> Result<MemoryGraph> {
+        if !(-1.0..=1.0).contains(&threshold) {
+            anyhow::bail!("similarity threshold must be between -1.0 and 1.0");
+        }
+        let documents = self.active_vector_documents()?;
+        let vectors = documents
+            .iter()
+            .map(|(_, vector)| normalized(vector))
+            .collect::<Vec<_>>();
+
+        let nodes = documents
+            .iter()
+            .map(|(record, _)| GraphNode {
+                kind: record.kind().to_string(),
+                id: record.id().to_string(),
+                label: record.text_for_ranking(),
+            })
+            .collect();
+        Ok(MemoryGraph {
+            nodes,
+            edges: similarity_edges(&vectors, threshold),
+        })
+    }
+
+    fn active_vector_documents(&self) -> Result<Vec<(MemoryRecord, Vec<f32>)>> {
+        let documents = self.with_vector_index(|index| Ok(index.documents()))?;
+        let mut active = Vec::with_capacity(documents.len());
+        for (kind, id, vector) in documents {
+            if let Some(record) = self.db.get_active_memory_by_kind(&kind, &id)? {
+                active.push((record, vector));
+            }
+        }
+        Ok(active)
+    }
+}
+
+fn similarity_edges(vectors: &[Vec<f32>], threshold: f32) -> Vec<GraphEdge> {
+    let mut edges = Vec::new();
+    for source in 0..vectors.len() {
+        for target in source + 1..vectors.len() {
+            let similarity = dot(&vectors[source], &vectors[target]);
+            if similarity >= threshold {
+                edges.push(GraphEdge {
+                    source,
+                    target,
+                    similarity,
+                });
+            }
+        }
+    }
+    edges
+}
+
+fn kmeans(vectors: &[Vec<f32>], k: usize, iterations: usize) -> (Vec<usize>, Vec<Vec<f32>>) {
+    let mut centroids = farthest_first_centroids(vectors, k);
+    let mut assignments = vec![0; vectors.len()];
+
+    for _ in 0..iterations {
+        let mut changed = false;
+        for (index, vector) in vectors.iter().enumerate() {
+            let nearest = nearest_centroid(vector, &centroids);
+            if assignments[index] != nearest {
+                assignments[index] = nearest;
+                changed = true;
+            }
+        }
+
+        for (cluster, centroid) in centroids.iter_mut().enumerate() {
+            let mut sum = vec![0.0; centroid.len()];
+            let mut count = 0;
+            for (vector, assigned) in vectors.iter().zip(&assignments) {
+                if *assigned == cluster {
+                    for (total, value) in sum.iter_mut().zip(vector) {
+                        *total += value;
+                    }
+                    count += 1;
+                }
+            }
+            if count > 0 {
+                *centroid = normalized(&sum);
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    (assignments, centroids)
+}
+
+/// Deterministic seeding: start from the first vector, then repeatedly pick the vector least
+/// similar to every centroid chosen so far.
+fn farthest_first_centroids(vectors: &[Vec<f32>], k: usize) -> Vec<Vec<f32>> {
+    let mut centroids = vec![vectors[0].clone()];
+    while centroids.len() < k {
+        let next = vectors
+            .iter()
+            .min_by(|left, right| {
+                max_similarity(left, &centroids).total_cmp(&max_similarity(right, &centroids))
+            })
+            .expect("vectors should not be empty");
+        centroids.push(next.clone());
+    }
+    centroids
+}
+
+fn nearest_centroid(vector: &[f32], centroids: &[Vec<f32>]) -> usize {
+    centroids
+        .iter()
+        .enumerate()
+        .max_by(|(_, left), (_, right)| dot(vector, left).total_cmp(&dot(vector, right)))
+        .map(|(index, _)| index)
+        .unwrap_or_default()
+}
+
+fn max_similarity(vector: &[f32], centroids: &[Vec<f32>]) -> f32 {
+    centroids
+        .iter()
+        .map(|centroid| dot(vector, centroid))
+        .fold(f32::MIN, f32::max)
+}
+
+fn dot(left: &[f32], right: &[f32]) -> f32 {
+    left.iter()
+        .zip(right)
+        .map(|(left, right)| left * right)
+        .sum()
+}
+
+fn normalized(vector: &[f32]) -> Vec<f32> {
+    let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        return vector.to_vec();
+    }
+    vector.iter().map(|value| value / norm).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{kmeans, normalized, similarity_edges};
+
+    #[test]
+    fn kmeans_separates_orthogonal_groups() {
+        let vectors = vec![
+            vec![1.0, 0.0, 0.0],
+            vec![0.9, 0.1, 0.0],
+            vec![0.0, 1.0, 0.0],
+            vec![0.1, 0.9, 0.0],
+            vec![0.0, 0.0, 1.0],
+        ];
+
+        let (assignments, centroids) = kmeans(&vectors, 3, 20);
+
+        assert_eq!(centroids.len(), 3);
+        assert_eq!(assignments[0], assignments[1]);
+        assert_eq!(assignments[2], assignments[3]);
+        assert_ne!(assignments[0], assignments[2]);
+        assert_ne!(assignments[0], assignments[4]);
+        assert_ne!(assignments[2], assignments[4]);
+    }
+
+    #[test]
+    fn kmeans_with_single_cluster_assigns_everything_together() {
+        let vectors = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+
+        let (assignments, centroids) = kmeans(&vectors, 1, 10);
+
+        assert_eq!(centroids.len(), 1);
+        assert_eq!(assignments, vec![0, 0]);
+    }
+
+    #[test]
+    fn similarity_edges_keep_only_pairs_above_threshold() {
+        let vectors = [vec![1.0, 0.0], vec![0.8, 0.6], vec![0.6, 0.8]]
+            .iter()
+            .map(|vector| normalized(vector))
+            .collect::<Vec<_>>();
+
+        let edges = similarity_edges(&vectors, 0.7);
+
+        assert_eq!(edges.len(), 2);
+        assert_eq!((edges[0].source, edges[0].target), (0, 1));
+        assert_eq!((edges[1].source, edges[1].target), (1, 2));
+        assert!((edges[0].similarity - 0.8).abs() < 1e-6);
+    }
+}
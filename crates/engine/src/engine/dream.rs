@@ -10,7 +10,9 @@ use super::MemoryEngine;
 
 impl MemoryEngine {
     pub fn dream(&self, trigger: DreamTrigger) -> Result<DreamReport> {
-        self.run_dream(trigger)
+        let report = self.run_dream(trigger)?;
+        self.record_audit("dream", &[]);
+        Ok(report)
     }
 
     pub fn dream_full(&self, trigger: DreamTrigger) -> Result<DreamReport> {
@@ -26,6 +28,7 @@ impl MemoryEngine {
             }
         }
         self.refresh_derived_layers_for_full_dream(&mut merged)?;
+        self.record_audit("dream_full", &[]);
         Ok(merged)
     }
 
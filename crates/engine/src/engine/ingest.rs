@@ -1,11 +1,18 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
 
-use anyhow::Result;
+use anyhow::{bail, Result};
+use chrono::Utc;
 use tracing::warn;
 
 use crate::{
-    db::normalize_text,
-    types::{EntityInput, EntityRecord, EpisodeInput, FactInput},
+    db::{normalize_text, stable_episode_id, Database},
+    types::{
+        EngineConfig, EntityInput, EntityRecord, EpisodeInput, EpisodeRecord, ExtractionSource,
+        FactInput, ImportReport, UpsertResult,
+    },
     ExtractedEntity, ExtractedFact, ExtractionResult,
 };
 
@@ -28,16 +35,105 @@ impl StructuredEpisodeSummary {
 impl MemoryEngine {
     pub fn remember(&self, input: EpisodeInput) -> Result<String> {
         let episode = self.db.insert_episode(&input, None)?;
-        let summary =
-            self.ingest_episode_structure(&episode, input.entities, input.facts, false)?;
+        self.finish_remember(&episode, input, "remember")?;
+        Ok(episode.id)
+    }
+
+    /// Like `remember`, but an active episode with the same normalized content is refreshed
+    /// in place instead of being stored a second time. Manual entities and facts are still
+    /// attached to whichever episode ends up holding the content.
+    pub fn remember_upsert(&self, input: EpisodeInput) -> Result<UpsertResult> {
+        match self.db.find_active_episode_by_content(&input.content)? {
+            Some(existing) => self.refresh_episode(existing, input),
+            None => self.remember(input).map(UpsertResult::Inserted),
+        }
+    }
+
+    /// Like `remember_upsert`, but the episode id is derived from the normalized content, so
+    /// the same text maps to the same id in every store.
+    pub fn remember_stable(&self, input: EpisodeInput) -> Result<UpsertResult> {
+        let id = stable_episode_id(&input.content);
+        match self.db.get_episode(&id)? {
+            Some(existing)
+                if existing.archived_at.is_none() && existing.invalidated_at.is_none() =>
+            {
+                self.refresh_episode(existing, input)
+            }
+            Some(_) => anyhow::bail!("episode {id} already exists but is archived or invalidated"),
+            None => {
+                let episode = self.db.insert_episode_with_id(&id, &input, None)?;
+                self.finish_remember(&episode, input, "remember")?;
+                Ok(UpsertResult::Inserted(episode.id))
+            }
+        }
+    }
+
+    /// Copies every active episode from the store under `data_dir` into this one, keeping
+    /// episode ids and rebuilding their entities and facts. Episodes whose id already exists
+    /// here are skipped, so importing the same store twice is a no-op.
+    pub fn import_from(&self, data_dir: &Path) -> Result<ImportReport> {
+        let source_path = EngineConfig::new(data_dir).sqlite_path();
+        if !source_path.is_file() {
+            bail!("no memo store found at {}", source_path.display());
+        }
+        if source_path.canonicalize()? == self.config.sqlite_path().canonicalize()? {
+            bail!("cannot import a store into itself");
+        }
+
+        let source = Database::open(&source_path)?;
+        let mut report = ImportReport::default();
+        let mut imported_ids = Vec::new();
+        for episode in source.load_active_episodes()? {
+            if self.db.get_episode(&episode.id)?.is_some() {
+                report.skipped += 1;
+                continue;
+            }
+
+            let input = import_input(&source, &episode)?;
+            let record = self.db.insert_episode_with_id(&episode.id, &input, None)?;
+            let summary =
+                self.ingest_episode_structure(&record, input.entities, input.facts, false)?;
+            if summary.has_structure() {
+                self.db.mark_episode_structured(&record.id)?;
+            }
+            imported_ids.push(record.id);
+            report.imported += 1;
+        }
+
+        if !imported_ids.is_empty() {
+            self.refresh_l3_cache()?;
+            let ids = imported_ids.iter().map(String::as_str).collect::<Vec<_>>();
+            self.record_audit("import", &ids);
+        }
+        Ok(report)
+    }
+
+    fn refresh_episode(
+        &self,
+        existing: EpisodeRecord,
+        input: EpisodeInput,
+    ) -> Result<UpsertResult> {
+        let observed_at = input.recorded_at.unwrap_or_else(Utc::now);
+        self.db.touch_episode(&existing.id, observed_at)?;
+        self.finish_remember(&existing, input, "upsert")?;
+        Ok(UpsertResult::Updated(existing.id))
+    }
+
+    fn finish_remember(
+        &self,
+        episode: &EpisodeRecord,
+        input: EpisodeInput,
+        operation: &str,
+    ) -> Result<()> {
+        let summary = self.ingest_episode_structure(episode, input.entities, input.facts, false)?;
         if summary.has_structure() {
             self.db.mark_episode_structured(&episode.id)?;
         }
 
         self.refresh_l3_cache()?;
         self.refresh_session_cache(&episode.id, &input.content, summary.entity_records.values())?;
-
-        Ok(episode.id)
+        self.record_audit(operation, &[&episode.id]);
+        Ok(())
     }
 
     fn resolve_fact_entity(
@@ -235,6 +331,45 @@ impl MemoryEngine {
     }
 }
 
+fn import_input(source: &Database, episode: &EpisodeRecord) -> Result<EpisodeInput> {
+    let mut entities = Vec::new();
+    for entity_id in source.mentioned_entity_ids(&episode.id)? {
+        if let Some(entity) = source.get_entity(&entity_id)? {
+            entities.push(EntityInput {
+                entity_type: entity.entity_type,
+                name: entity.canonical_name,
+                aliases: entity.aliases,
+                confidence: entity.confidence,
+                source: ExtractionSource::Manual,
+            });
+        }
+    }
+
+    let mut facts = Vec::new();
+    for fact_id in source.active_related_ids_for_episode("fact", &episode.id)? {
+        if let Some(fact) = source.get_fact(&fact_id)? {
+            facts.push(FactInput {
+                subject: fact.subject_text,
+                predicate: fact.predicate,
+                object: fact.object_text,
+                confidence: fact.confidence,
+                source: ExtractionSource::Manual,
+            });
+        }
+    }
+
+    Ok(EpisodeInput {
+        content: episode.content.clone(),
+        layer: episode.layer,
+        entities,
+        facts,
+        source_episode_id: episode.source_episode_id.clone(),
+        session_id: episode.session_id.clone(),
+        recorded_at: Some(episode.created_at),
+        confidence: episode.confidence,
+    })
+}
+
 fn merge_entities(manual: Vec<EntityInput>, extracted: Vec<ExtractedEntity>) -> Vec<EntityInput> {
     let mut merged: HashMap<String, EntityInput> = HashMap::new();
     for entity in manual {
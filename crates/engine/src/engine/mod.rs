@@ -1,14 +1,17 @@
 use std::{collections::HashMap, sync::Mutex};
 
 use anyhow::{Context, Result};
+use tracing::warn;
 
 use crate::{
+    audit_log::AuditLog,
     db::Database,
     text_index::TextIndex,
     types::{EngineConfig, MemoryRecord, RecallReason},
     vector_index::VectorIndex,
 };
 
+mod analysis;
 mod dream;
 mod ingest;
 mod recall;
@@ -20,7 +23,9 @@ pub struct MemoryEngine {
     config: EngineConfig,
     db: Database,
     text_index: Mutex<TextIndex>,
-    vector_index: Mutex<VectorIndex>,
+    /// Opened on first use: loading rebuilds the HNSW graph, and most commands never read it.
+    vector_index: Mutex<Option<VectorIndex>>,
+    audit_log: AuditLog,
     l3_cache: Mutex<HashMap<String, MemoryRecord>>,
     session: Mutex<SessionCache>,
 }
@@ -38,21 +43,24 @@ struct Candidate {
     memory: MemoryRecord,
     score: f32,
     reasons: Vec<RecallReason>,
+    /// Recency multiplier applied to `score`, kept so the result reports the same value.
+    recency_score: Option<f32>,
 }
 
 impl MemoryEngine {
     pub fn open(config: EngineConfig) -> Result<Self> {
         config.ensure_dirs()?;
 
-        let db = Database::open(&config.sqlite_path())?;
+        let db = Database::open(&config.sqlite_path())?.with_id_scheme(config.id_scheme);
         let text_index = TextIndex::open(&config.text_index_dir())?;
-        let vector_index = VectorIndex::open(config.vector_index_path(), config.vector_dimension)?;
+        let audit_log = AuditLog::new(config.audit_log_path());
 
         let engine = Self {
             config,
             db,
             text_index: Mutex::new(text_index),
-            vector_index: Mutex::new(vector_index),
+            vector_index: Mutex::new(None),
+            audit_log,
             l3_cache: Mutex::new(HashMap::new()),
             session: Mutex::new(SessionCache::default()),
         };
@@ -71,18 +79,52 @@ impl MemoryEngine {
     }
 
     pub fn pin(&self, kind: &str, id: &str, reason: Option<&str>) -> Result<()> {
-        self.db.pin_record(kind, id, reason)
+        self.db.pin_record(kind, id, reason)?;
+        self.record_audit("pin", &[id]);
+        Ok(())
     }
 
     pub fn unpin(&self, kind: &str, id: &str) -> Result<()> {
-        self.db.unpin_record(kind, id)
+        self.db.unpin_record(kind, id)?;
+        self.record_audit("unpin", &[id]);
+        Ok(())
     }
 
     pub fn anchor(&self, kind: &str, id: &str) -> Result<()> {
-        self.db.anchor_record(kind, id)
+        self.db.anchor_record(kind, id)?;
+        self.record_audit("anchor", &[id]);
+        Ok(())
     }
 
     pub fn unanchor(&self, kind: &str, id: &str) -> Result<()> {
-        self.db.unanchor_record(kind, id)
+        self.db.unanchor_record(kind, id)?;
+        self.record_audit("unanchor", &[id]);
+        Ok(())
+    }
+
+    /// Loads `vector-index.json` and returns how many documents it holds. Fails when the file
+    /// cannot be decoded or holds vectors of another dimension.
+    pub fn check_vector_index(&self) -> Result<usize> {
+        self.with_vector_index(|index| Ok(index.document_count()))
+    }
+
+    /// Runs `f` against the vector index, opening it on first use.
+    fn with_vector_index<T>(&self, f: impl FnOnce(&mut VectorIndex) -> Result<T>) -> Result<T> {
+        let mut guard = self.vector_index.lock().expect("vector mutex poisoned");
+        if guard.is_none() {
+            *guard = Some(VectorIndex::open(
+                self.config.vector_index_path(),
+                self.config.vector_dimension,
+            )?);
+        }
+        f(guard.as_mut().expect("vector index opened above"))
+    }
+
+    /// The write has already been committed, so a failed audit append is logged rather than
+    /// surfaced as an error.
+    fn record_audit(&self, operation: &str, ids: &[&str]) {
+        if let Err(error) = self.audit_log.append(operation, ids) {
+            warn!(operation, error = %error, "failed to append audit log entry");
+        }
     }
 }
@@ -4,7 +4,7 @@ use std::{
 };
 
 use anyhow::{Context, Result};
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::{
     db::normalize_text,
@@ -31,8 +31,16 @@ enum RecallSearchStrategy {
 }
 
 impl MemoryEngine {
-    pub fn recall(&self, request: RecallRequest) -> Result<RecallResultSet> {
+    pub fn recall(&self, mut request: RecallRequest) -> Result<RecallResultSet> {
         let started = Instant::now();
+        if request.limit > self.config.max_recall_limit {
+            warn!(
+                requested = request.limit,
+                max = self.config.max_recall_limit,
+                "recall limit capped by max_recall_limit"
+            );
+            request.limit = self.config.max_recall_limit;
+        }
         let mut result = self.execute_query(&request, request.deep)?;
         if !request.deep
             && matches!(
@@ -34,6 +34,7 @@ impl MemoryEngine {
                     memory: record,
                     score: 3.0,
                     reasons: vec![reason],
+                    recency_score: None,
                 },
             );
         }
@@ -48,6 +49,7 @@ impl MemoryEngine {
                             memory,
                             score: 0.4 + hit.score.max(0.0) * 0.15,
                             reasons: vec![RecallReason::Bm25],
+                            recency_score: None,
                         },
                     );
                 }
@@ -66,6 +68,7 @@ impl MemoryEngine {
                     memory,
                     score: 0.35 / hops as f32,
                     reasons: vec![RecallReason::GraphHop { hops }],
+                    recency_score: None,
                 },
             );
         }
@@ -77,6 +80,7 @@ impl MemoryEngine {
                     memory,
                     score: 1.45,
                     reasons: vec![RecallReason::WorkingSet],
+                    recency_score: None,
                 },
             );
         }
@@ -123,6 +127,10 @@ impl MemoryEngine {
             if has_subject_mismatch(&request.query, &candidate.memory) {
                 candidate.reasons.push(RecallReason::SubjectMismatch);
             }
+            candidate.recency_score = self.recency_factor(&candidate.memory);
+            if let Some(factor) = candidate.recency_score {
+                candidate.score *= factor;
+            }
             scored.push(candidate);
         }
         scored.sort_by(|a, b| b.score.total_cmp(&a.score));
@@ -143,6 +151,7 @@ impl MemoryEngine {
             .map(|mut candidate| {
                 candidate.reasons.push(RecallReason::MmrSelected);
                 RecallResult {
+                    recency_score: candidate.recency_score,
                     memory: candidate.memory,
                     score: candidate.score,
                     reasons: candidate.reasons,
@@ -157,6 +166,13 @@ impl MemoryEngine {
             capabilities,
         })
     }
+    fn recency_factor(&self, memory: &MemoryRecord) -> Option<f32> {
+        recency_factor(
+            memory.activity_at(),
+            self.config.recency_weight,
+            self.config.recency_half_life_days,
+        )
+    }
     fn session_cache_match(&self, normalized_query: &str) -> Result<Option<Candidate>> {
         let session = self.session.lock().expect("session mutex poisoned");
         let Some(entity_id) = session.recent_aliases.get(normalized_query).cloned() else {
@@ -171,6 +187,7 @@ impl MemoryEngine {
             memory,
             score: 3.5,
             reasons: vec![RecallReason::SessionCache],
+            recency_score: None,
         }))
     }
     fn l3_matches(&self, normalized_query: &str) -> Result<Vec<Candidate>> {
@@ -186,6 +203,7 @@ impl MemoryEngine {
                     memory: record.clone(),
                     score: 2.4,
                     reasons: vec![RecallReason::L3],
+                    recency_score: None,
                 });
             }
         }
@@ -119,6 +119,19 @@ pub(super) fn recency_boost(updated_at: chrono::DateTime<chrono::Utc>) -> f32 {
     let age_days = (chrono::Utc::now() - updated_at).num_days().max(0) as f32;
     (-(age_days / 30.0)).exp() * 0.18
 }
+pub(super) fn recency_factor(
+    updated_at: chrono::DateTime<chrono::Utc>,
+    weight: f32,
+    half_life_days: u32,
+) -> Option<f32> {
+    if weight <= 0.0 {
+        return None;
+    }
+    let weight = weight.min(1.0);
+    let age_days = (chrono::Utc::now() - updated_at).num_days().max(0) as f32;
+    let decay = 0.5_f32.powf(age_days / half_life_days.max(1) as f32);
+    Some((1.0 - weight) + weight * decay)
+}
 pub(super) fn hit_frequency_boost(hit_count: u64) -> f32 {
     ((hit_count as f32) + 1.0).ln() * 0.05
 }
@@ -4,7 +4,7 @@ use crate::types::{
     EpisodeRecord, MemoryLayer, MemoryRecord, RecallCapabilities, RecallResult, RecallResultSet,
 };
 
-use super::ranking::{pinned_boost, query_coverage, query_subject_tokens};
+use super::ranking::{pinned_boost, query_coverage, query_subject_tokens, recency_factor};
 use super::{session_cache::trim_session_cache, strategy::should_auto_escalate_to_deep_search};
 use crate::engine::SessionCache;
 
@@ -13,6 +13,7 @@ fn episode_result(score: f32, reasons: Vec<crate::types::RecallReason>) -> Recal
         memory: episode_record("episode-1", "Paris travel checklist for May."),
         score,
         reasons,
+        recency_score: None,
     }
 }
 
@@ -145,3 +146,18 @@ fn pinned_boost_requires_meaningful_query_match() {
     assert!(pinned_boost("fleet telemetry plan", &matching) > 0.0);
     assert_eq!(pinned_boost("fleet telemetry plan", &weak), 0.0);
 }
+
+#[test]
+fn recency_factor_is_disabled_without_weight() {
+    assert_eq!(recency_factor(Utc::now(), 0.0, 180), None);
+}
+
+#[test]
+fn recency_factor_halves_weighted_share_after_one_half_life() {
+    let fresh = recency_factor(Utc::now(), 0.4, 180).expect("expected recency factor");
+    let aged = recency_factor(Utc::now() - chrono::Duration::days(180), 0.4, 180)
+        .expect("expected recency factor");
+
+    assert!((fresh - 1.0).abs() < 1e-6);
+    assert!((aged - 0.8).abs() < 1e-3);
+}
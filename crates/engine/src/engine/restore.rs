@@ -9,6 +9,12 @@ use crate::{
 use super::MemoryEngine;
 
 impl MemoryEngine {
+    /// Compacts the SQLite store. Derived indexes are untouched; memory records do not change,
+    /// so nothing is written to the audit log.
+    pub fn vacuum(&self) -> Result<()> {
+        self.db.vacuum()
+    }
+
     pub fn restore_full(&self, scope: RestoreScope) -> Result<RestoreReport> {
         let mut report = RestoreReport::default();
 
@@ -27,11 +33,7 @@ impl MemoryEngine {
 
         if matches!(scope, RestoreScope::All | RestoreScope::Vector) {
             let docs = self.db.load_vector_documents()?;
-            let count = self
-                .vector_index
-                .lock()
-                .expect("vector mutex poisoned")
-                .rebuild(&docs)?;
+            let count = self.with_vector_index(|index| index.rebuild(&docs))?;
             self.db.clear_all_index_jobs("vector")?;
             self.db
                 .record_index_ready("vector", count, Some("vector derived refresh complete"))?;
@@ -166,8 +168,7 @@ impl MemoryEngine {
                     }),
                 })
                 .collect::<Result<Vec<_>>>()?;
-            let mut vector_index = self.vector_index.lock().expect("vector mutex poisoned");
-            vector_index.apply_updates(&updates)
+            self.with_vector_index(|index| index.apply_updates(&updates))
         })();
 
         match outcome {
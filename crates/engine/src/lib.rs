@@ -1,20 +1,25 @@
+mod audit_log;
 mod db;
 mod engine;
 pub mod eval;
+#[cfg(feature = "test-utils")]
+pub mod mock;
 mod model;
 mod text_index;
 mod types;
 mod vector_index;
 
+pub use audit_log::read_audit_log;
 pub use engine::{Engine, MemoryEngine};
 pub use model::{
     EmbeddingProvider, ExtractedEntity, ExtractedFact, ExtractionProvider, ExtractionResult,
     RerankProvider, RerankScore,
 };
 pub use types::{
-    DreamProviderCallSummary, DreamReport, DreamTrigger, EdgeRecord, EngineConfig, EntityInput,
-    EntityRecord, EpisodeInput, EpisodeRecord, ExtractionSource, FactInput, FactRecord,
-    IndexStatus, LayerState, LayerSummary, MemoryLayer, MemoryRecord, RecallCapabilities,
+    AuditEntry, ClusterMember, DreamProviderCallSummary, DreamReport, DreamTrigger, EdgeRecord,
+    EngineConfig, EntityInput, EntityRecord, EpisodeInput, EpisodeRecord, ExtractionSource,
+    FactInput, FactRecord, GraphEdge, GraphNode, IdScheme, ImportReport, IndexStatus, LayerState,
+    LayerSummary, MemoryCluster, MemoryGraph, MemoryLayer, MemoryRecord, RecallCapabilities,
     RecallReason, RecallRequest, RecallResult, RecallResultSet, RestoreReport, RestoreScope,
-    SystemState,
+    SystemState, UpsertResult,
 };
@@ -0,0 +1,122 @@
+//! Deterministic providers for tests that need embeddings or reranking without a real backend.
+
+use anyhow::Result;
+
+use crate::{EmbeddingProvider, RerankProvider, RerankScore};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hashes each lowercased whitespace token into one dimension and L2-normalizes the counts, so
+/// the same text always embeds the same way and texts sharing words land close together.
+#[derive(Debug, Clone)]
+pub struct MockEmbeddingProvider {
+    dimension: usize,
+}
+
+impl MockEmbeddingProvider {
+    pub fn new(dimension: usize) -> Self {
+        assert!(dimension > 0, "mock embedding dimension must be positive");
+        Self { dimension }
+    }
+}
+
+impl Default for MockEmbeddingProvider {
+    fn default() -> Self {
+        Self::new(16)
+    }
+}
+
+impl EmbeddingProvider for MockEmbeddingProvider {
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn embed_text(&self, text: &str) -> Result<Vec<f32>> {
+        let mut vector = vec![0.0_f32; self.dimension];
+        for token in text.split_whitespace() {
+            let bucket = fnv1a(&token.to_lowercase()) % self.dimension as u64;
+            vector[bucket as usize] += 1.0;
+        }
+        let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|value| *value /= norm);
+        }
+        Ok(vector)
+    }
+}
+
+/// Keeps documents in input order with scores falling linearly from `1.0`.
+#[derive(Debug, Clone, Default)]
+pub struct MockRerankProvider;
+
+impl RerankProvider for MockRerankProvider {
+    fn rerank(&self, _query: &str, documents: &[String]) -> Result<Vec<RerankScore>> {
+        let count = documents.len() as f32;
+        Ok((0..documents.len())
+            .map(|index| RerankScore {
+                index,
+                score: 1.0 - index as f32 / count,
+            })
+            .collect())
+    }
+}
+
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::{MockEmbeddingProvider, MockRerankProvider};
+    use crate::{EmbeddingProvider, RerankProvider};
+
+    fn dot(left: &[f32], right: &[f32]) -> f32 {
+        left.iter().zip(right).map(|(l, r)| l * r).sum()
+    }
+
+    #[test]
+    fn mock_embeddings_are_deterministic_and_normalized() -> Result<()> {
+        let provider = MockEmbeddingProvider::new(8);
+
+        let first = provider.embed_text("Alice keeps a launch checklist")?;
+        let second = provider.embed_text("alice keeps a  launch checklist")?;
+
+        assert_eq!(first.len(), 8);
+        assert_eq!(first, second);
+        assert!((dot(&first, &first) - 1.0).abs() < 1e-5);
+        assert!(provider.embed_text("")?.iter().all(|value| *value == 0.0));
+        Ok(())
+    }
+
+    #[test]
+    fn mock_embeddings_score_shared_words_higher() -> Result<()> {
+        let provider = MockEmbeddingProvider::new(64);
+        let query = provider.embed_text("launch checklist")?;
+
+        let related = provider.embed_text("Alice keeps a launch checklist")?;
+        let unrelated = provider.embed_text("Bob prefers paper notes")?;
+
+        assert!(dot(&query, &related) > dot(&query, &unrelated));
+        Ok(())
+    }
+
+    #[test]
+    fn mock_rerank_keeps_input_order_with_decreasing_scores() -> Result<()> {
+        let documents = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+
+        let scores = MockRerankProvider.rerank("query", &documents)?;
+
+        assert_eq!(
+            scores.iter().map(|score| score.index).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert!(scores.windows(2).all(|pair| pair[0].score > pair[1].score));
+        assert!(MockRerankProvider.rerank("query", &[])?.is_empty());
+        Ok(())
+    }
+}
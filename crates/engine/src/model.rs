@@ -36,6 +36,7 @@ pub struct RerankScore {
 
 pub trait EmbeddingProvider: Send + Sync {
     fn dimension(&self) -> usize;
+    /// Embeds stored memory content.
     fn embed_text(&self, text: &str) -> Result<Vec<f32>>;
 }
 
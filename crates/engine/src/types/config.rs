@@ -4,6 +4,7 @@ use std::{
 };
 
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use crate::{EmbeddingProvider, ExtractionProvider, RerankProvider};
 
@@ -62,11 +63,56 @@ impl LayerState {
     }
 }
 
+/// How new record ids are generated. Existing ids are never rewritten, so switching schemes
+/// only changes records written afterwards.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum IdScheme {
+    /// Random UUIDv4.
+    #[default]
+    V4,
+    /// Time-ordered UUIDv7, so ids sort by creation time.
+    V7,
+}
+
+impl IdScheme {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::V4 => "v4",
+            Self::V7 => "v7",
+        }
+    }
+
+    pub fn new_id(self) -> String {
+        match self {
+            Self::V4 => Uuid::new_v4().to_string(),
+            Self::V7 => Uuid::now_v7().to_string(),
+        }
+    }
+}
+
+impl std::str::FromStr for IdScheme {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "v4" => Ok(Self::V4),
+            "v7" => Ok(Self::V7),
+            _ => anyhow::bail!("invalid id scheme: {} (expected v4 or v7)", s),
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct EngineConfig {
     pub data_dir: PathBuf,
     pub vector_dimension: usize,
     pub l3_cache_limit: usize,
+    /// Share of the recall score that decays with memory age; `0.0` disables the weighting.
+    pub recency_weight: f32,
+    pub recency_half_life_days: u32,
+    pub id_scheme: IdScheme,
+    /// Upper bound applied to `RecallRequest::limit`.
+    pub max_recall_limit: usize,
     #[serde(skip)]
     pub embedding_provider: Option<Arc<dyn EmbeddingProvider>>,
     #[serde(skip)]
@@ -81,6 +127,10 @@ impl EngineConfig {
             data_dir: data_dir.into(),
             vector_dimension: 384,
             l3_cache_limit: 256,
+            recency_weight: 0.0,
+            recency_half_life_days: 180,
+            id_scheme: IdScheme::V4,
+            max_recall_limit: 1000,
             embedding_provider: None,
             rerank_provider: None,
             extraction_provider: None,
@@ -99,6 +149,10 @@ impl EngineConfig {
         self.data_dir.join("vector-index.json")
     }
 
+    pub fn audit_log_path(&self) -> PathBuf {
+        self.data_dir.join("audit.log")
+    }
+
     pub fn with_embedding_provider(mut self, provider: Arc<dyn EmbeddingProvider>) -> Self {
         self.vector_dimension = provider.dimension();
         self.embedding_provider = Some(provider);
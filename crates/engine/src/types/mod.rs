@@ -4,11 +4,12 @@ mod recall;
 mod record;
 mod reports;
 
-pub use config::{EngineConfig, LayerState, MemoryLayer};
+pub use config::{EngineConfig, IdScheme, LayerState, MemoryLayer};
 pub use input::{EntityInput, EpisodeInput, ExtractionSource, FactInput};
 pub use recall::{RecallCapabilities, RecallReason, RecallRequest, RecallResult, RecallResultSet};
 pub use record::{EdgeRecord, EntityRecord, EpisodeRecord, FactRecord, MemoryRecord};
 pub use reports::{
-    DreamProviderCallSummary, DreamReport, DreamTrigger, IndexStatus, LayerSummary, RestoreReport,
-    RestoreScope, SystemState,
+    AuditEntry, ClusterMember, DreamProviderCallSummary, DreamReport, DreamTrigger, GraphEdge,
+    GraphNode, ImportReport, IndexStatus, LayerSummary, MemoryCluster, MemoryGraph, RestoreReport,
+    RestoreScope, SystemState, UpsertResult,
 };
@@ -31,6 +31,8 @@ pub struct RecallResult {
     pub score: f32,
     #[serde(default)]
     pub reasons: Vec<RecallReason>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recency_score: Option<f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -115,6 +116,81 @@ pub struct SystemState {
     pub vector_index: IndexStatus,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub operation: String,
+    /// Empty for whole-store operations such as `dream`, which report their changes as counts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ids: Vec<String>,
+    pub pid: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ImportReport {
+    pub imported: usize,
+    pub skipped: usize,
+}
+
+/// Outcome of `remember_upsert` / `remember_stable`, carrying the episode id either way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpsertResult {
+    Inserted(String),
+    Updated(String),
+}
+
+impl UpsertResult {
+    pub fn id(&self) -> &str {
+        match self {
+            Self::Inserted(id) | Self::Updated(id) => id,
+        }
+    }
+
+    pub fn status(&self) -> &'static str {
+        match self {
+            Self::Inserted(_) => "inserted",
+            Self::Updated(_) => "updated",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryCluster {
+    pub label_kind: String,
+    pub label_id: String,
+    pub label: String,
+    pub members: Vec<ClusterMember>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterMember {
+    pub kind: String,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    pub kind: String,
+    pub id: String,
+    pub label: String,
+}
+
+/// Undirected edge between two entries of `MemoryGraph::nodes`, referenced by index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub source: usize,
+    pub target: usize,
+    pub similarity: f32,
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -190,6 +190,22 @@ impl VectorIndex {
     pub fn document_count(&self) -> usize {
         self.records.len()
     }
+
+    /// Returns `(kind, id, vector)` for every stored document, ordered by storage key.
+    pub fn documents(&self) -> Vec<(String, String, Vec<f32>)> {
+        let mut keys = self.records.keys().collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys.into_iter()
+            .map(|key| {
+                let record = &self.records[key];
+                (
+                    record.kind.clone(),
+                    record.id.clone(),
+                    record.vector.clone(),
+                )
+            })
+            .collect()
+    }
 }
 
 fn load_records(path: &Path) -> Result<HashMap<String, StoredVector>> {
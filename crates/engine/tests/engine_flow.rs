@@ -9,10 +9,11 @@ use std::{
 
 use anyhow::Result;
 use memo_engine::{
-    DreamTrigger, EmbeddingProvider, EngineConfig, EntityInput, EpisodeInput, ExtractedEntity,
-    ExtractedFact, ExtractionProvider, ExtractionResult, ExtractionSource, FactInput, MemoryEngine,
-    MemoryLayer, MemoryRecord, RecallReason, RecallRequest, RerankProvider, RerankScore,
-    RestoreScope,
+    mock::MockEmbeddingProvider, read_audit_log, DreamTrigger, EmbeddingProvider, EngineConfig,
+    EntityInput, EpisodeInput, ExtractedEntity, ExtractedFact, ExtractionProvider,
+    ExtractionResult, ExtractionSource, FactInput, IdScheme, MemoryEngine, MemoryLayer,
+    MemoryRecord, RecallReason, RecallRequest, RerankProvider, RerankScore, RestoreScope,
+    UpsertResult,
 };
 use rusqlite::Connection;
 use tempfile::TempDir;
@@ -378,6 +379,188 @@ fn alias_query_hits_entity_record() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn write_operations_append_audit_log_entries() -> Result<()> {
+    let temp = TempDir::new()?;
+    let engine = open_engine(temp.path())?;
+    let episode_id = engine.remember(episode_input("Alice keeps a launch checklist."))?;
+    engine.pin("episode", &episode_id, Some("important"))?;
+    let _ = engine.recall(RecallRequest {
+        query: "Alice launch".to_string(),
+        limit: 5,
+        deep: false,
+        include_related_records: false,
+    })?;
+    engine.dream(DreamTrigger::Manual)?;
+
+    let entries = read_audit_log(&temp.path().join("audit.log"))?;
+    let operations = entries
+        .iter()
+        .map(|entry| entry.operation.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(operations, vec!["remember", "pin", "dream"]);
+    assert_eq!(entries[0].ids, vec![episode_id.clone()]);
+    assert_eq!(entries[1].ids, vec![episode_id]);
+    assert!(entries[2].ids.is_empty());
+    Ok(())
+}
+
+#[test]
+fn remember_upsert_refreshes_matching_episode_instead_of_duplicating() -> Result<()> {
+    let temp = TempDir::new()?;
+    let engine = open_engine(temp.path())?;
+
+    let first = engine.remember_upsert(episode_input("Alice keeps a launch checklist."))?;
+    let second = engine.remember_upsert(episode_input("Alice keeps a launch checklist."))?;
+    let other = engine.remember_upsert(episode_input("Bob prefers paper notes."))?;
+
+    assert!(matches!(first, UpsertResult::Inserted(_)));
+    assert_eq!(second, UpsertResult::Updated(first.id().to_string()));
+    assert!(matches!(other, UpsertResult::Inserted(_)));
+    assert_eq!(engine.state()?.episode_count, 2);
+
+    let operations = read_audit_log(&temp.path().join("audit.log"))?
+        .into_iter()
+        .map(|entry| entry.operation)
+        .collect::<Vec<_>>();
+    assert_eq!(operations, vec!["remember", "upsert", "remember"]);
+    Ok(())
+}
+
+#[test]
+fn remember_stable_derives_episode_id_from_content() -> Result<()> {
+    let first_dir = TempDir::new()?;
+    let second_dir = TempDir::new()?;
+    let first_engine = open_engine(first_dir.path())?;
+    let second_engine = open_engine(second_dir.path())?;
+
+    let first = first_engine.remember_stable(episode_input("Alice keeps a launch checklist."))?;
+    let again = first_engine.remember_stable(episode_input("alice keeps a  launch checklist."))?;
+    let elsewhere =
+        second_engine.remember_stable(episode_input("Alice keeps a launch checklist."))?;
+
+    assert!(matches!(first, UpsertResult::Inserted(_)));
+    assert_eq!(again, UpsertResult::Updated(first.id().to_string()));
+    assert_eq!(elsewhere, UpsertResult::Inserted(first.id().to_string()));
+    assert_eq!(first_engine.state()?.episode_count, 1);
+    Ok(())
+}
+
+#[test]
+fn recall_caps_limit_at_max_recall_limit() -> Result<()> {
+    let temp = TempDir::new()?;
+    let mut config = EngineConfig::new(temp.path());
+    config.max_recall_limit = 1;
+    let engine = MemoryEngine::open(config)?;
+    engine.remember(episode_input("Alice keeps a launch checklist."))?;
+    engine.remember(episode_input("Alice reviews the launch checklist."))?;
+    engine.restore(RestoreScope::Text)?;
+
+    let result = engine.recall(RecallRequest {
+        query: "launch checklist".to_string(),
+        limit: 100,
+        deep: false,
+        include_related_records: false,
+    })?;
+
+    assert_eq!(result.results.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn import_from_copies_episodes_with_structure_and_skips_existing_ids() -> Result<()> {
+    let source_dir = TempDir::new()?;
+    let target_dir = TempDir::new()?;
+    let source = open_engine(source_dir.path())?;
+    let target = open_engine(target_dir.path())?;
+
+    let mut input = episode_input("Alice works at Memo.");
+    input.facts = vec![FactInput {
+        subject: "Alice".to_string(),
+        predicate: "works_at".to_string(),
+        object: "Memo".to_string(),
+        confidence: 0.9,
+        source: ExtractionSource::Manual,
+    }];
+    let structured_id = source.remember(input)?;
+    source.remember(episode_input("Bob prefers paper notes."))?;
+    target.remember(episode_input("Carol runs the release train."))?;
+    drop(source);
+
+    let report = target.import_from(source_dir.path())?;
+    assert_eq!(report.imported, 2);
+    assert_eq!(report.skipped, 0);
+
+    let state = target.state()?;
+    assert_eq!(state.episode_count, 3);
+    assert_eq!(state.fact_count, 1);
+    assert_eq!(target.reflect(&structured_id)?.id(), structured_id);
+
+    let again = target.import_from(source_dir.path())?;
+    assert_eq!(again.imported, 0);
+    assert_eq!(again.skipped, 2);
+    assert!(target.import_from(target_dir.path()).is_err());
+    Ok(())
+}
+
+#[test]
+fn vector_index_is_opened_only_when_first_needed() -> Result<()> {
+    let temp = TempDir::new()?;
+    let config = EngineConfig::new(temp.path());
+    config.ensure_dirs()?;
+    std::fs::write(config.vector_index_path(), b"not json")?;
+
+    let engine = MemoryEngine::open(config)?;
+    engine.remember(episode_input("Alice keeps a launch checklist."))?;
+    engine.restore(RestoreScope::Text)?;
+    let result = engine.recall(RecallRequest {
+        query: "launch checklist".to_string(),
+        limit: 5,
+        deep: false,
+        include_related_records: false,
+    })?;
+    assert_eq!(result.results.len(), 1);
+
+    let error = engine
+        .cluster(2, 10)
+        .expect_err("corrupt vector index should fail on first vector read");
+    assert!(format!("{error:#}").contains("failed to decode vector index file"));
+    Ok(())
+}
+
+#[test]
+fn vacuum_keeps_records_readable() -> Result<()> {
+    let temp = TempDir::new()?;
+    let engine = open_engine(temp.path())?;
+    let id = engine.remember(episode_input("Alice keeps a launch checklist."))?;
+    engine.remember(episode_input("Bob prefers paper notes."))?;
+
+    engine.vacuum()?;
+
+    assert_eq!(engine.state()?.episode_count, 2);
+    assert!(matches!(engine.reflect(&id)?, MemoryRecord::Episode(record) if record.id == id));
+    let wal = temp.path().join("memory.db-wal");
+    assert!(!wal.exists() || std::fs::metadata(&wal)?.len() == 0);
+    Ok(())
+}
+
+#[test]
+fn v7_id_scheme_writes_time_ordered_ids() -> Result<()> {
+    let temp = TempDir::new()?;
+    let mut config = EngineConfig::new(temp.path());
+    config.id_scheme = IdScheme::V7;
+    let engine = MemoryEngine::open(config)?;
+
+    let first = engine.remember(episode_input("Alice keeps a launch checklist."))?;
+    let second = engine.remember(episode_input("Bob prefers paper notes."))?;
+
+    for id in [&first, &second] {
+        assert_eq!(uuid::Uuid::parse_str(id)?.get_version_num(), 7);
+    }
+    assert!(first < second);
+    Ok(())
+}
+
 #[test]
 fn remember_entity_alias_reuses_existing_entity_record() -> Result<()> {
     let temp = TempDir::new()?;
@@ -1305,6 +1488,46 @@ fn ambiguous_query_auto_escalates_to_deep_search() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn dream_embeds_extracted_structure_with_mock_embedding_provider() -> Result<()> {
+    struct CountingMockEmbeddingProvider {
+        inner: MockEmbeddingProvider,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl EmbeddingProvider for CountingMockEmbeddingProvider {
+        fn dimension(&self) -> usize {
+            self.inner.dimension()
+        }
+
+        fn embed_text(&self, text: &str) -> Result<Vec<f32>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.inner.embed_text(text)
+        }
+    }
+
+    let temp = TempDir::new()?;
+    let calls = Arc::new(AtomicUsize::new(0));
+    let engine = MemoryEngine::open(
+        EngineConfig::new(temp.path())
+            .with_extraction_provider(Arc::new(TestExtractionProvider))
+            .with_embedding_provider(Arc::new(CountingMockEmbeddingProvider {
+                inner: MockEmbeddingProvider::default(),
+                calls: Arc::clone(&calls),
+            })),
+    )?;
+    engine.remember(episode_input("Alice lives in Paris."))?;
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+    let report = engine.dream(DreamTrigger::Manual)?;
+
+    assert_eq!(report.structured_episodes, 1);
+    assert!(calls.load(Ordering::SeqCst) > 0);
+    assert!(report.derived_vector_documents > 0);
+    assert!(engine.state()?.vector_index.doc_count > 0);
+    Ok(())
+}
+
 #[test]
 fn deep_recall_keeps_results_when_rerank_provider_fails() -> Result<()> {
     let temp = TempDir::new()?;
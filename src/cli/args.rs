@@ -1,21 +1,53 @@
-use anyhow::Result;
-use chrono::{DateTime, Utc};
-use clap::{Parser, Subcommand};
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
 use memo_engine::{EntityInput, EpisodeInput, ExtractionSource, FactInput, MemoryLayer};
 
+use crate::cli::output::FieldSet;
+
 #[derive(Debug, Parser)]
 #[command(name = "memo")]
 #[command(about = "Local single-process memory engine")]
 pub(crate) struct Cli {
+    /// Disable ANSI colors in log output; setting `NO_COLOR` to any value does the same.
+    #[arg(long = "no-color", global = true)]
+    pub(crate) no_color: bool,
+    #[arg(long = "log-format", global = true, value_enum, default_value_t = LogFormat::Text)]
+    pub(crate) log_format: LogFormat,
+    /// Append log output to this file instead of writing it to the terminal.
+    #[arg(long = "log-file", global = true)]
+    pub(crate) log_file: Option<PathBuf>,
+    /// Apply the `[profiles.<name>.*]` overrides from config.toml; same as `MEMO_PROFILE`.
+    #[arg(long, global = true)]
+    pub(crate) profile: Option<String>,
     #[command(subcommand)]
     pub(crate) command: Command,
 }
 
+impl Cli {
+    pub(crate) fn use_color(&self) -> bool {
+        !self.no_color && std::env::var_os("NO_COLOR").is_none()
+    }
+}
+
 #[derive(Debug, Subcommand)]
 pub(crate) enum Command {
     Awaken,
     Remember {
-        content: String,
+        #[arg(required_unless_present_any = ["file", "stdin"], conflicts_with_all = ["file", "stdin"])]
+        content: Option<String>,
+        /// Read the episode content from a file.
+        #[arg(long, conflicts_with = "stdin")]
+        file: Option<PathBuf>,
+        /// Read the episode content from standard input.
+        #[arg(long)]
+        stdin: bool,
         #[arg(long = "time")]
         time: Option<String>,
         #[arg(long = "entity")]
@@ -23,16 +55,57 @@ pub(crate) enum Command {
         #[arg(long = "fact")]
         facts: Vec<String>,
         #[arg(long)]
+        upsert: bool,
+        #[arg(long = "stable-id", conflicts_with = "upsert")]
+        stable_id: bool,
+        #[arg(long)]
+        json: bool,
+    },
+    Bench {
+        /// Number of generated episodes to remember.
+        #[arg(long, default_value_t = 100)]
+        texts: usize,
+        /// Number of generated recall queries to run.
+        #[arg(long, default_value_t = 100)]
+        queries: usize,
+        #[arg(long = "remember-only", conflicts_with = "recall_only")]
+        remember_only: bool,
+        #[arg(long = "recall-only")]
+        recall_only: bool,
+        /// Write into the configured store instead of a scratch directory.
+        #[arg(long)]
+        persist: bool,
+        #[arg(long)]
+        json: bool,
+    },
+    Vacuum {
+        #[arg(long)]
+        json: bool,
+    },
+    Import {
+        /// Data directory of the memo store to copy episodes from.
+        #[arg(long)]
+        from: PathBuf,
+        #[arg(long)]
         json: bool,
     },
     Recall {
-        query: String,
+        #[arg(required_unless_present = "interactive")]
+        query: Option<String>,
         #[arg(short = 'n', long, default_value_t = 10)]
         limit: usize,
         #[arg(long)]
         deep: bool,
+        #[arg(long = "boost-recent")]
+        boost_recent: bool,
         #[arg(long)]
         json: bool,
+        #[arg(long, value_enum, conflicts_with = "json")]
+        format: Option<RecallFormat>,
+        #[arg(long, value_parser = FieldSet::parse)]
+        fields: Option<FieldSet>,
+        #[arg(long, conflicts_with = "query")]
+        interactive: bool,
     },
     Reflect {
         id: String,
@@ -49,6 +122,144 @@ pub(crate) enum Command {
         #[arg(long)]
         json: bool,
     },
+    Info {
+        #[arg(long)]
+        json: bool,
+    },
+    Doctor {
+        #[arg(long)]
+        json: bool,
+    },
+    Audit {
+        #[arg(long)]
+        last: Option<usize>,
+        #[arg(long, value_parser = parse_since)]
+        since: Option<DateTime<Utc>>,
+        #[arg(long)]
+        json: bool,
+    },
+    Cluster {
+        #[arg(long, default_value_t = 10)]
+        k: usize,
+        #[arg(long, default_value_t = 100)]
+        iterations: usize,
+        #[arg(long)]
+        output: Option<PathBuf>,
+        #[arg(long)]
+        json: bool,
+    },
+    Graph {
+        #[arg(long, default_value_t = 0.7)]
+        threshold: f32,
+        #[arg(long)]
+        output: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+    },
+    #[command(name = "saved-recall")]
+    SavedRecall {
+        #[command(subcommand)]
+        command: SavedRecallCommand,
+    },
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    Provider {
+        #[command(subcommand)]
+        command: ProviderCommand,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum LogFormat {
+    Text,
+    /// One JSON object per log event.
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum RecallFormat {
+    Text,
+    Json,
+    /// One compact JSON object per result line.
+    Jsonl,
+    /// Fixed-width columns, one result per row.
+    Table,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum GraphFormat {
+    Dot,
+    Graphml,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum SavedRecallCommand {
+    Save {
+        name: String,
+        query: String,
+        #[arg(short = 'n', long, default_value_t = 10)]
+        limit: usize,
+        #[arg(long)]
+        deep: bool,
+        #[arg(long = "boost-recent")]
+        boost_recent: bool,
+    },
+    List {
+        #[arg(long)]
+        json: bool,
+    },
+    Run {
+        name: String,
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum ConfigCommand {
+    Validate {
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum ProviderCommand {
+    Test {
+        name: String,
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Picks the episode text from whichever source `remember` was given; clap guarantees that
+/// exactly one of the argument, `--file`, or `--stdin` is set. Text read from a file or stdin
+/// loses its trailing line break and must not be blank.
+pub(crate) fn resolve_remember_content(
+    content: Option<String>,
+    file: Option<&Path>,
+    stdin: Option<impl Read>,
+) -> Result<String> {
+    let content = match (content, file, stdin) {
+        (Some(content), _, _) => return Ok(content),
+        (None, Some(path), _) => fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?,
+        (None, None, Some(mut reader)) => {
+            let mut content = String::new();
+            reader
+                .read_to_string(&mut content)
+                .context("failed to read content from stdin")?;
+            content
+        }
+        (None, None, None) => anyhow::bail!("remember requires content, --file, or --stdin"),
+    };
+    let content = content.trim_end_matches(['\r', '\n']).to_string();
+    if content.trim().is_empty() {
+        anyhow::bail!("remember content is empty");
+    }
+    Ok(content)
 }
 
 pub(crate) fn build_remember_input(
@@ -138,10 +349,29 @@ fn parse_recorded_at(raw: Option<&str>) -> Result<Option<DateTime<Utc>>> {
     .transpose()
 }
 
+/// Accepts an RFC 3339 timestamp or a bare `YYYY-MM-DD` date, read as midnight UTC.
+fn parse_since(raw: &str) -> Result<DateTime<Utc>> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(raw, "%Y-%m-%d").map_err(|_| {
+        anyhow::anyhow!("expected an RFC 3339 timestamp or YYYY-MM-DD date, got `{raw}`")
+    })?;
+    Ok(date
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is a valid time")
+        .and_utc())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Cli, Command};
+    use super::{
+        resolve_remember_content, Cli, Command, ConfigCommand, FieldSet, GraphFormat, LogFormat,
+        ProviderCommand, RecallFormat, SavedRecallCommand,
+    };
+    use anyhow::Result;
     use clap::Parser;
+    use std::path::PathBuf;
 
     #[test]
     fn cli_parses_awaken_without_path_argument() {
@@ -175,19 +405,246 @@ mod tests {
         assert!(error.to_string().contains("unexpected argument"));
     }
 
+    #[test]
+    fn cli_requires_exactly_one_remember_content_source() {
+        let cli = Cli::parse_from(["memo", "remember", "--file", "notes.md"]);
+        match cli.command {
+            Command::Remember {
+                content,
+                file,
+                stdin,
+                ..
+            } => {
+                assert_eq!(content, None);
+                assert_eq!(file, Some(PathBuf::from("notes.md")));
+                assert!(!stdin);
+            }
+            _ => panic!("expected remember command"),
+        }
+
+        assert!(Cli::try_parse_from(["memo", "remember"]).is_err());
+        assert!(Cli::try_parse_from(["memo", "remember", "text", "--stdin"]).is_err());
+        assert!(
+            Cli::try_parse_from(["memo", "remember", "--file", "notes.md", "--stdin"]).is_err()
+        );
+    }
+
+    #[test]
+    fn resolve_remember_content_reads_file_or_stdin() -> Result<()> {
+        let temp = tempfile::TempDir::new()?;
+        let path = temp.path().join("notes.md");
+        std::fs::write(&path, "Alice keeps a launch checklist.\n")?;
+
+        assert_eq!(
+            resolve_remember_content(None, Some(&path), None::<&[u8]>)?,
+            "Alice keeps a launch checklist."
+        );
+        assert_eq!(
+            resolve_remember_content(None, None, Some("Bob prefers paper notes.\r\n".as_bytes()))?,
+            "Bob prefers paper notes."
+        );
+        assert_eq!(
+            resolve_remember_content(Some("inline".to_string()), None, None::<&[u8]>)?,
+            "inline"
+        );
+        assert!(resolve_remember_content(None, None, Some("  \n".as_bytes())).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn cli_parses_remember_upsert_flag() {
+        let cli = Cli::parse_from(["memo", "remember", "Alice lives in Paris.", "--upsert"]);
+
+        match cli.command {
+            Command::Remember { upsert, .. } => assert!(upsert),
+            _ => panic!("expected remember command"),
+        }
+    }
+
+    #[test]
+    fn cli_rejects_remember_stable_id_with_upsert() {
+        let error = Cli::try_parse_from([
+            "memo",
+            "remember",
+            "Alice lives in Paris.",
+            "--stable-id",
+            "--upsert",
+        ])
+        .expect_err("expected --stable-id and --upsert to conflict");
+
+        assert!(error.to_string().contains("cannot be used with"));
+    }
+
+    #[test]
+    fn cli_parses_global_no_color_flag() {
+        let cli = Cli::parse_from(["memo", "state", "--no-color"]);
+        assert!(cli.no_color);
+        assert!(!cli.use_color());
+
+        let cli = Cli::parse_from(["memo", "--no-color", "recall", "Alice"]);
+        assert!(cli.no_color);
+        assert!(!Cli::parse_from(["memo", "state"]).no_color);
+    }
+
+    #[test]
+    fn cli_parses_global_log_options() {
+        let cli = Cli::parse_from(["memo", "state"]);
+        assert_eq!(cli.log_format, LogFormat::Text);
+        assert_eq!(cli.log_file, None);
+
+        let cli = Cli::parse_from([
+            "memo",
+            "dream",
+            "--log-format",
+            "json",
+            "--log-file",
+            "/tmp/memo.log",
+        ]);
+        assert_eq!(cli.log_format, LogFormat::Json);
+        assert_eq!(cli.log_file, Some(PathBuf::from("/tmp/memo.log")));
+        assert!(Cli::try_parse_from(["memo", "state", "--log-format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn cli_parses_bench_options() {
+        let cli = Cli::parse_from(["memo", "bench", "--texts", "20", "--recall-only"]);
+
+        match cli.command {
+            Command::Bench {
+                texts,
+                queries,
+                remember_only,
+                recall_only,
+                persist,
+                ..
+            } => {
+                assert_eq!(texts, 20);
+                assert_eq!(queries, 100);
+                assert!(!remember_only);
+                assert!(recall_only);
+                assert!(!persist);
+            }
+            _ => panic!("expected bench command"),
+        }
+        assert!(
+            Cli::try_parse_from(["memo", "bench", "--remember-only", "--recall-only"]).is_err()
+        );
+    }
+
+    #[test]
+    fn cli_parses_global_profile_flag() {
+        let cli = Cli::parse_from(["memo", "recall", "Alice", "--profile", "fast"]);
+        assert_eq!(cli.profile.as_deref(), Some("fast"));
+        assert_eq!(Cli::parse_from(["memo", "state"]).profile, None);
+    }
+
+    #[test]
+    fn cli_parses_vacuum_json_flag() {
+        let cli = Cli::parse_from(["memo", "vacuum", "--json"]);
+        assert!(matches!(cli.command, Command::Vacuum { json: true }));
+    }
+
+    #[test]
+    fn cli_parses_import_from_path() {
+        let cli = Cli::parse_from(["memo", "import", "--from", "/tmp/other-memo", "--json"]);
+
+        match cli.command {
+            Command::Import { from, json } => {
+                assert_eq!(from, PathBuf::from("/tmp/other-memo"));
+                assert!(json);
+            }
+            _ => panic!("expected import command"),
+        }
+        assert!(Cli::try_parse_from(["memo", "import"]).is_err());
+    }
+
     #[test]
     fn cli_parses_recall_deep_flag() {
         let cli = Cli::parse_from(["memo", "recall", "Alice", "--deep"]);
 
         match cli.command {
             Command::Recall { query, deep, .. } => {
-                assert_eq!(query, "Alice");
+                assert_eq!(query.as_deref(), Some("Alice"));
                 assert!(deep);
             }
             _ => panic!("expected recall command"),
         }
     }
 
+    #[test]
+    fn cli_parses_recall_boost_recent_flag() {
+        let cli = Cli::parse_from(["memo", "recall", "Alice", "--boost-recent"]);
+
+        match cli.command {
+            Command::Recall { boost_recent, .. } => assert!(boost_recent),
+            _ => panic!("expected recall command"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_interactive_recall_without_query() {
+        let cli = Cli::parse_from(["memo", "recall", "--interactive", "-n", "3"]);
+
+        match cli.command {
+            Command::Recall {
+                query,
+                limit,
+                interactive,
+                ..
+            } => {
+                assert!(query.is_none());
+                assert_eq!(limit, 3);
+                assert!(interactive);
+            }
+            _ => panic!("expected recall command"),
+        }
+    }
+
+    #[test]
+    fn cli_requires_recall_query_without_interactive() {
+        assert!(Cli::try_parse_from(["memo", "recall"]).is_err());
+    }
+
+    #[test]
+    fn cli_parses_recall_jsonl_format() {
+        let cli = Cli::parse_from(["memo", "recall", "Alice", "--format", "jsonl"]);
+
+        match cli.command {
+            Command::Recall { json, format, .. } => {
+                assert!(!json);
+                assert_eq!(format, Some(RecallFormat::Jsonl));
+            }
+            _ => panic!("expected recall command"),
+        }
+    }
+
+    #[test]
+    fn cli_rejects_recall_format_with_json_flag() {
+        let result =
+            Cli::try_parse_from(["memo", "recall", "Alice", "--json", "--format", "jsonl"]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn cli_parses_recall_fields() {
+        let cli = Cli::parse_from(["memo", "recall", "Alice", "--fields", "id, content,score"]);
+
+        match cli.command {
+            Command::Recall { fields, .. } => {
+                assert_eq!(fields, Some(FieldSet::parse("score,content,id").unwrap()));
+            }
+            _ => panic!("expected recall command"),
+        }
+    }
+
+    #[test]
+    fn cli_rejects_unknown_recall_field() {
+        let result = Cli::try_parse_from(["memo", "recall", "Alice", "--fields", "id,tags"]);
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn cli_parses_dream_full_flag() {
         let cli = Cli::parse_from(["memo", "dream", "--full"]);
@@ -221,6 +678,140 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_parses_cluster_options() {
+        let cli = Cli::parse_from(["memo", "cluster", "--k", "4", "--output", "clusters.json"]);
+
+        match cli.command {
+            Command::Cluster {
+                k,
+                iterations,
+                output,
+                ..
+            } => {
+                assert_eq!(k, 4);
+                assert_eq!(iterations, 100);
+                assert_eq!(
+                    output.as_deref(),
+                    Some(std::path::Path::new("clusters.json"))
+                );
+            }
+            _ => panic!("expected cluster command"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_graph_format_and_threshold() {
+        let cli = Cli::parse_from([
+            "memo",
+            "graph",
+            "--threshold",
+            "0.85",
+            "--format",
+            "graphml",
+        ]);
+
+        match cli.command {
+            Command::Graph {
+                threshold,
+                output,
+                format,
+            } => {
+                assert_eq!(threshold, 0.85);
+                assert!(output.is_none());
+                assert_eq!(format, GraphFormat::Graphml);
+            }
+            _ => panic!("expected graph command"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_saved_recall_save() {
+        let cli = Cli::parse_from([
+            "memo",
+            "saved-recall",
+            "save",
+            "alice",
+            "where does Alice live",
+            "-n",
+            "5",
+            "--deep",
+        ]);
+
+        match cli.command {
+            Command::SavedRecall {
+                command:
+                    SavedRecallCommand::Save {
+                        name,
+                        query,
+                        limit,
+                        deep,
+                        boost_recent,
+                    },
+            } => {
+                assert_eq!(name, "alice");
+                assert_eq!(query, "where does Alice live");
+                assert_eq!(limit, 5);
+                assert!(deep);
+                assert!(!boost_recent);
+            }
+            _ => panic!("expected saved-recall save command"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_audit_since_date_and_timestamp() {
+        let cli = Cli::parse_from(["memo", "audit", "--since", "2026-04-21", "--last", "5"]);
+
+        match cli.command {
+            Command::Audit { last, since, .. } => {
+                assert_eq!(last, Some(5));
+                assert_eq!(
+                    since.map(|value| value.to_rfc3339()),
+                    Some("2026-04-21T00:00:00+00:00".to_string())
+                );
+            }
+            _ => panic!("expected audit command"),
+        }
+
+        let cli = Cli::parse_from(["memo", "audit", "--since", "2026-04-21T10:00:00+02:00"]);
+        match cli.command {
+            Command::Audit { since, .. } => assert_eq!(
+                since.map(|value| value.to_rfc3339()),
+                Some("2026-04-21T08:00:00+00:00".to_string())
+            ),
+            _ => panic!("expected audit command"),
+        }
+        assert!(Cli::try_parse_from(["memo", "audit", "--since", "yesterday"]).is_err());
+    }
+
+    #[test]
+    fn cli_parses_config_validate_json_flag() {
+        let cli = Cli::parse_from(["memo", "config", "validate", "--json"]);
+
+        match cli.command {
+            Command::Config {
+                command: ConfigCommand::Validate { json },
+            } => assert!(json),
+            _ => panic!("expected config validate command"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_provider_test_name() {
+        let cli = Cli::parse_from(["memo", "provider", "test", "aliyun"]);
+
+        match cli.command {
+            Command::Provider {
+                command: ProviderCommand::Test { name, json },
+            } => {
+                assert_eq!(name, "aliyun");
+                assert!(!json);
+            }
+            _ => panic!("expected provider test command"),
+        }
+    }
+
     #[test]
     fn cli_rejects_restore_command() {
         let error = Cli::try_parse_from(["memo", "restore"])
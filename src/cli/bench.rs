@@ -0,0 +1,230 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+use memo_engine::{EpisodeInput, MemoryEngine, MemoryLayer, RecallRequest, RestoreScope};
+use tracing::warn;
+
+use crate::cli::output::{BenchOperation, BenchReport};
+
+const BENCH_WORDS: &[&str] = &[
+    "alice",
+    "bob",
+    "carol",
+    "launch",
+    "checklist",
+    "paris",
+    "release",
+    "train",
+    "notes",
+    "warehouse",
+    "drones",
+    "budget",
+    "review",
+    "design",
+    "meeting",
+    "garden",
+    "coffee",
+    "travel",
+    "invoice",
+    "roadmap",
+    "sprint",
+    "deploy",
+    "server",
+    "printer",
+    "library",
+    "museum",
+    "piano",
+    "dentist",
+    "flight",
+    "harbor",
+];
+const WORDS_PER_TEXT: usize = 8;
+const WORDS_PER_QUERY: usize = 2;
+const BENCH_RECALL_LIMIT: usize = 10;
+
+/// What `memo bench` measures and where it writes.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BenchOptions {
+    pub texts: usize,
+    pub queries: usize,
+    pub remember: bool,
+    pub recall: bool,
+}
+
+/// Runs the remember and recall passes against `engine`. Generated texts come from a fixed
+/// seed, so two runs with the same options write and query the same content.
+pub(crate) fn run_bench(engine: &MemoryEngine, options: BenchOptions) -> Result<BenchReport> {
+    let mut rng = Lcg::new(0x6d65_6d6f);
+    let mut operations = Vec::new();
+
+    if options.remember {
+        let mut timings = Vec::with_capacity(options.texts);
+        for _ in 0..options.texts {
+            let input = bench_episode(rng.sentence(WORDS_PER_TEXT));
+            let started = Instant::now();
+            engine.remember(input)?;
+            timings.push(started.elapsed());
+        }
+        operations.push(summarize("remember", timings));
+    }
+
+    if options.recall {
+        engine.restore(RestoreScope::Text)?;
+        let mut timings = Vec::with_capacity(options.queries);
+        for _ in 0..options.queries {
+            let request = RecallRequest {
+                query: rng.sentence(WORDS_PER_QUERY),
+                limit: BENCH_RECALL_LIMIT,
+                deep: false,
+                include_related_records: false,
+            };
+            let started = Instant::now();
+            engine.recall(request)?;
+            timings.push(started.elapsed());
+        }
+        operations.push(summarize("recall", timings));
+    }
+
+    Ok(BenchReport { operations })
+}
+
+/// A fresh directory under the system temp dir for benchmarks that should not touch the
+/// configured store.
+pub(crate) fn scratch_data_dir() -> Result<PathBuf> {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+    let dir = std::env::temp_dir().join(format!("memo-bench-{}-{nanos}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+pub(crate) fn remove_scratch_data_dir(dir: &Path) {
+    if let Err(error) = fs::remove_dir_all(dir) {
+        warn!(
+            path = %dir.display(),
+            error = %error,
+            "failed to remove bench scratch directory"
+        );
+    }
+}
+
+fn bench_episode(content: String) -> EpisodeInput {
+    EpisodeInput {
+        content,
+        layer: MemoryLayer::L1,
+        entities: Vec::new(),
+        facts: Vec::new(),
+        source_episode_id: None,
+        session_id: None,
+        recorded_at: None,
+        confidence: 0.9,
+    }
+}
+
+fn summarize(operation: &'static str, mut timings: Vec<Duration>) -> BenchOperation {
+    timings.sort();
+    BenchOperation {
+        operation,
+        count: timings.len(),
+        p50_ms: percentile_ms(&timings, 50.0),
+        p95_ms: percentile_ms(&timings, 95.0),
+        p99_ms: percentile_ms(&timings, 99.0),
+        total_ms: timings.iter().sum::<Duration>().as_secs_f64() * 1000.0,
+    }
+}
+
+/// Nearest-rank percentile over timings that are already sorted ascending.
+fn percentile_ms(sorted: &[Duration], percentile: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (percentile / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1].as_secs_f64() * 1000.0
+}
+
+/// Small linear congruential generator so bench content is reproducible without a rand
+/// dependency.
+struct Lcg(u64);
+
+impl Lcg {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_index(&mut self, len: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        ((self.0 >> 33) % len as u64) as usize
+    }
+
+    fn sentence(&mut self, words: usize) -> String {
+        (0..words)
+            .map(|_| BENCH_WORDS[self.next_index(BENCH_WORDS.len())])
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use anyhow::Result;
+    use memo_engine::{EngineConfig, MemoryEngine};
+    use tempfile::TempDir;
+
+    use super::{percentile_ms, run_bench, BenchOptions, Lcg};
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let timings = (1..=100).map(Duration::from_millis).collect::<Vec<_>>();
+
+        assert_eq!(percentile_ms(&timings, 50.0), 50.0);
+        assert_eq!(percentile_ms(&timings, 95.0), 95.0);
+        assert_eq!(percentile_ms(&timings, 99.0), 99.0);
+        assert_eq!(percentile_ms(&timings[..1], 99.0), 1.0);
+        assert_eq!(percentile_ms(&[], 50.0), 0.0);
+    }
+
+    #[test]
+    fn generated_sentences_are_reproducible() {
+        let mut first = Lcg::new(7);
+        let mut second = Lcg::new(7);
+
+        let sentence = first.sentence(8);
+        assert_eq!(sentence, second.sentence(8));
+        assert_eq!(sentence.split(' ').count(), 8);
+    }
+
+    #[test]
+    fn run_bench_reports_requested_operations() -> Result<()> {
+        let temp = TempDir::new()?;
+        let engine = MemoryEngine::open(EngineConfig::new(temp.path()))?;
+
+        let report = run_bench(
+            &engine,
+            BenchOptions {
+                texts: 5,
+                queries: 3,
+                remember: true,
+                recall: true,
+            },
+        )?;
+
+        assert_eq!(
+            report
+                .operations
+                .iter()
+                .map(|operation| (operation.operation, operation.count))
+                .collect::<Vec<_>>(),
+            vec![("remember", 5), ("recall", 3)]
+        );
+        assert_eq!(engine.state()?.episode_count, 5);
+        Ok(())
+    }
+}
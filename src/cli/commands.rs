@@ -1,67 +1,186 @@
-use std::path::PathBuf;
+use std::{fs, io, path::PathBuf};
 
-use anyhow::Result;
-use memo_engine::{DreamTrigger, MemoryEngine, RecallRequest};
+use anyhow::{Context, Result};
+use memo_engine::{
+    read_audit_log, DreamTrigger, EngineConfig, MemoryEngine, RecallRequest, RecallResultSet,
+};
 
 use crate::{
     cli::{
-        args::{build_remember_input, Cli, Command},
+        args::{
+            build_remember_input, resolve_remember_content, Cli, Command, ConfigCommand,
+            GraphFormat, ProviderCommand, RecallFormat, SavedRecallCommand,
+        },
+        bench::{self, BenchOptions},
         output::{
-            render_awaken_result, render_dream_report, render_json_or_text, render_recall_result,
-            render_reflection, render_state,
+            render_audit_log, render_awaken_result, render_bench_report, render_clusters,
+            render_config_validation, render_doctor, render_dream_report, render_graph_dot,
+            render_graph_graphml, render_info, render_json_or_text, render_recall_result,
+            render_recall_table, render_reflection, render_saved_recalls, render_state,
+            render_vacuum_report, write_recall_jsonl, FieldSet, InfoReport,
         },
-        paths::{default_config_dir, resolve_data_dir_for_config_dir},
+        paths::{
+            default_config_dir, directory_size, resolve_data_dir_for_config_dir, resolve_profile,
+        },
+        repl::{open_query_editor, run_recall_repl},
     },
-    config,
-    providers::status,
+    config::{self, SavedRecall},
+    providers::{health::ProviderCheck, status},
 };
 
+/// Recency weight used by `recall --boost-recent` when `engine.recency_weight` is not set.
+const BOOST_RECENT_DEFAULT_WEIGHT: f32 = 0.3;
+const RECALL_HISTORY_FILE: &str = "history";
+
 pub(crate) fn run(cli: Cli) -> Result<()> {
+    let profile = resolve_profile(cli.profile);
+    let profile = profile.as_deref();
     match cli.command {
         Command::Awaken => {
             let config_dir = default_config_dir()?;
-            let data_dir = resolve_data_dir_for_config_dir(&config_dir)?;
+            let data_dir = resolve_data_dir_for_config_dir(&config_dir, profile)?;
             let report = config::initialize_app_home(&config_dir, &data_dir)?;
             println!("{}", render_awaken_result(&data_dir, &config_dir, &report));
         }
         Command::Remember {
             content,
+            file,
+            stdin,
             time,
             entities,
             facts,
+            upsert,
+            stable_id,
             json,
         } => {
-            let engine = open_engine()?;
+            let engine = open_engine(profile)?;
+            let content = resolve_remember_content(
+                content,
+                file.as_deref(),
+                stdin.then(|| io::stdin().lock()),
+            )?;
             let input = build_remember_input(content, time, &entities, &facts)?;
 
-            let id = engine.remember(input)?;
+            if upsert || stable_id {
+                let result = if stable_id {
+                    engine.remember_stable(input)?
+                } else {
+                    engine.remember_upsert(input)?
+                };
+                println!(
+                    "{}",
+                    render_json_or_text(
+                        &serde_json::json!({ "id": result.id(), "status": result.status() }),
+                        result.id(),
+                        json,
+                    )?
+                );
+            } else {
+                let id = engine.remember(input)?;
+                println!(
+                    "{}",
+                    render_json_or_text(&serde_json::json!({ "id": id }), &id, json)?
+                );
+            }
+        }
+        Command::Bench {
+            texts,
+            queries,
+            remember_only,
+            recall_only,
+            persist,
+            json,
+        } => {
+            let config_dir = default_config_dir()?;
+            let scratch_dir = (!persist && !recall_only)
+                .then(bench::scratch_data_dir)
+                .transpose()?;
+            let data_dir = match &scratch_dir {
+                Some(dir) => dir.clone(),
+                None => resolve_data_dir_for_config_dir(&config_dir, profile)?,
+            };
+            let engine = MemoryEngine::open(config::build_engine_config(
+                &data_dir,
+                &config_dir,
+                profile,
+            )?)?;
+            let report = bench::run_bench(
+                &engine,
+                BenchOptions {
+                    texts,
+                    queries,
+                    remember: !recall_only,
+                    recall: !remember_only,
+                },
+            );
+            drop(engine);
+            if let Some(dir) = &scratch_dir {
+                bench::remove_scratch_data_dir(dir);
+            }
+            println!("{}", render_bench_report(&report?, json)?);
+        }
+        Command::Vacuum { json } => {
+            let (engine, data_dir) = open_engine_with_data_dir(profile)?;
+            let before_bytes = directory_size(&data_dir)?;
+            engine.vacuum()?;
+            let after_bytes = directory_size(&data_dir)?;
             println!(
                 "{}",
-                render_json_or_text(&serde_json::json!({ "id": id }), &id, json)?
+                render_vacuum_report(&data_dir, before_bytes, after_bytes, json)?
             );
         }
+        Command::Import { from, json } => {
+            let engine = open_engine(profile)?;
+            let report = engine.import_from(&from)?;
+            let text = format!("imported: {}\nskipped: {}", report.imported, report.skipped);
+            println!("{}", render_json_or_text(&report, &text, json)?);
+        }
         Command::Recall {
             query,
             limit,
             deep,
+            boost_recent,
             json,
+            format,
+            fields,
+            interactive,
         } => {
-            let engine = open_engine()?;
-            let result = engine.recall(RecallRequest {
-                query,
-                limit,
-                deep,
-                include_related_records: false,
-            })?;
-            println!("{}", render_recall_result(&result, json)?);
+            let engine = open_recall_engine(profile, boost_recent)?;
+            let format = format.unwrap_or(if json {
+                RecallFormat::Json
+            } else {
+                RecallFormat::Text
+            });
+            if interactive {
+                let config_dir = default_config_dir()?;
+                let history_file = config_dir.join(RECALL_HISTORY_FILE);
+                let history_path = config_dir.is_dir().then_some(history_file.as_path());
+                let mut editor = open_query_editor(history_path)?;
+                run_recall_repl(&mut editor, &mut io::stdout(), history_path, |query| {
+                    let result = recall(&engine, query.to_string(), limit, deep)?;
+                    render_recall_output(&result, format, fields.as_ref())
+                })?;
+            } else {
+                let query = query.context("recall requires a query unless --interactive is set")?;
+                let result = recall(&engine, query, limit, deep)?;
+                match format {
+                    RecallFormat::Jsonl => {
+                        write_recall_jsonl(&result, fields.as_ref(), &mut io::stdout().lock())?
+                    }
+                    _ => println!(
+                        "{}",
+                        render_recall_output(&result, format, fields.as_ref())?
+                    ),
+                }
+            }
         }
         Command::Reflect { id, json } => {
-            let engine = open_engine()?;
+            let engine = open_engine(profile)?;
             let record = engine.reflect(&id)?;
             println!("{}", render_reflection(&record, json)?);
         }
         Command::Dream { full, json } => {
-            let engine = open_engine()?;
+            let engine = open_engine(profile)?;
             let report = if full {
                 engine.dream_full(DreamTrigger::Manual)?
             } else {
@@ -70,29 +189,233 @@ pub(crate) fn run(cli: Cli) -> Result<()> {
             println!("{}", render_dream_report(&report, full, json)?);
         }
         Command::State { json } => {
-            let (engine, data_dir) = open_engine_with_data_dir()?;
+            let (engine, data_dir) = open_engine_with_data_dir(profile)?;
             let state = engine.state()?;
             let provider_runtime = status::load_provider_runtime_summary(&data_dir);
             let config_dir = default_config_dir()?;
             let provider_readiness =
-                config::load_provider_readiness(&config_dir, &provider_runtime);
+                config::load_provider_readiness(&config_dir, profile, &provider_runtime);
             println!(
                 "{}",
                 render_state(&state, &provider_runtime, &provider_readiness, json)?
             );
         }
+        Command::Info { json } => {
+            let config_dir = default_config_dir()?;
+            let data_dir = resolve_data_dir_for_config_dir(&config_dir, profile)?;
+            let engine_config = config::build_engine_config(&data_dir, &config_dir, profile)?;
+            let vector_dimension = engine_config.vector_dimension;
+            let vector_index_exists = engine_config.vector_index_path().exists();
+            let engine = MemoryEngine::open(engine_config)?;
+            let state = engine.state()?;
+            let vector_documents = engine.check_vector_index()?;
+            let provider_runtime = status::load_provider_runtime_summary(&data_dir);
+            let embedding_provider =
+                config::load_provider_readiness(&config_dir, profile, &provider_runtime)
+                    .capabilities
+                    .into_iter()
+                    .find(|capability| capability.capability == "embedding")
+                    .and_then(|capability| capability.provider_ref);
+            let report = InfoReport {
+                version: env!("CARGO_PKG_VERSION"),
+                data_size_bytes: directory_size(&data_dir)?,
+                config_dir,
+                data_dir,
+                episode_count: state.episode_count,
+                entity_count: state.entity_count,
+                fact_count: state.fact_count,
+                edge_count: state.edge_count,
+                embedding_provider,
+                vector_dimension,
+                vector_index_exists,
+                vector_documents,
+            };
+            println!("{}", render_info(&report, json)?);
+        }
+        Command::Cluster {
+            k,
+            iterations,
+            output,
+            json,
+        } => {
+            let engine = open_engine(profile)?;
+            let clusters = engine.cluster(k, iterations)?;
+            if let Some(path) = output {
+                fs::write(&path, serde_json::to_string_pretty(&clusters)?).with_context(|| {
+                    format!("failed to write cluster assignments: {}", path.display())
+                })?;
+            }
+            println!("{}", render_clusters(&clusters, json)?);
+        }
+        Command::Graph {
+            threshold,
+            output,
+            format,
+        } => {
+            let engine = open_engine(profile)?;
+            let graph = engine.similarity_graph(threshold)?;
+            let rendered = match format {
+                GraphFormat::Dot => render_graph_dot(&graph),
+                GraphFormat::Graphml => render_graph_graphml(&graph),
+            };
+            match output {
+                Some(path) => {
+                    fs::write(&path, rendered).with_context(|| {
+                        format!("failed to write similarity graph: {}", path.display())
+                    })?;
+                    println!(
+                        "Wrote {} node(s) and {} edge(s) to {}",
+                        graph.nodes.len(),
+                        graph.edges.len(),
+                        path.display()
+                    );
+                }
+                None => println!("{rendered}"),
+            }
+        }
+        Command::SavedRecall { command } => match command {
+            SavedRecallCommand::Save {
+                name,
+                query,
+                limit,
+                deep,
+                boost_recent,
+            } => {
+                let config_dir = default_config_dir()?;
+                let replaced = config::save_recall(
+                    &config_dir,
+                    SavedRecall {
+                        name: name.clone(),
+                        query,
+                        limit,
+                        deep,
+                        boost_recent,
+                    },
+                )?;
+                println!(
+                    "{} saved recall `{name}`",
+                    if replaced { "Updated" } else { "Added" }
+                );
+            }
+            SavedRecallCommand::List { json } => {
+                let config_dir = default_config_dir()?;
+                let recalls = config::load_saved_recalls(&config_dir)?;
+                println!("{}", render_saved_recalls(&recalls, json)?);
+            }
+            SavedRecallCommand::Run { name, json } => {
+                let config_dir = default_config_dir()?;
+                let saved = config::find_saved_recall(&config_dir, &name)?;
+                let engine = open_recall_engine(profile, saved.boost_recent)?;
+                let result = recall(&engine, saved.query, saved.limit, saved.deep)?;
+                println!("{}", render_recall_result(&result, json, None)?);
+            }
+        },
+        Command::Audit { last, since, json } => {
+            let config_dir = default_config_dir()?;
+            let data_dir = resolve_data_dir_for_config_dir(&config_dir, profile)?;
+            let entries = read_audit_log(&EngineConfig::new(&data_dir).audit_log_path())?
+                .into_iter()
+                .filter(|entry| !since.is_some_and(|since| entry.timestamp < since))
+                .collect::<Vec<_>>();
+            let skip = last.map_or(0, |last| entries.len().saturating_sub(last));
+            println!("{}", render_audit_log(&entries[skip..], json)?);
+        }
+        Command::Doctor { json } => {
+            let config_dir = default_config_dir()?;
+            let data_dir = resolve_data_dir_for_config_dir(&config_dir, profile)?;
+            let checks = config::run_doctor(&config_dir, profile, &data_dir);
+            println!("{}", render_doctor(&checks, json)?);
+            let failed = checks.iter().filter(|check| !check.ok).count();
+            if failed > 0 {
+                anyhow::bail!("{failed} doctor check(s) failed");
+            }
+        }
+        Command::Config {
+            command: ConfigCommand::Validate { json },
+        } => {
+            let config_dir = default_config_dir()?;
+            let checks = config::validate_providers(&config_dir, profile)?;
+            println!("{}", render_config_validation(&checks, json)?);
+            ensure_provider_checks_passed(&checks)?;
+        }
+        Command::Provider {
+            command: ProviderCommand::Test { name, json },
+        } => {
+            let config_dir = default_config_dir()?;
+            let checks = config::test_provider(&config_dir, profile, &name)?;
+            println!("{}", render_config_validation(&checks, json)?);
+            ensure_provider_checks_passed(&checks)?;
+        }
     }
 
     Ok(())
 }
 
-fn open_engine() -> Result<MemoryEngine> {
-    Ok(open_engine_with_data_dir()?.0)
+fn ensure_provider_checks_passed(checks: &[ProviderCheck]) -> Result<()> {
+    let failed = checks.iter().filter(|check| !check.ok).count();
+    if failed > 0 {
+        anyhow::bail!("{failed} provider check(s) failed");
+    }
+    Ok(())
+}
+
+fn open_recall_engine(profile: Option<&str>, boost_recent: bool) -> Result<MemoryEngine> {
+    open_engine_with(profile, |config| {
+        if boost_recent && config.recency_weight <= 0.0 {
+            config.recency_weight = BOOST_RECENT_DEFAULT_WEIGHT;
+        }
+    })
+}
+
+fn recall(
+    engine: &MemoryEngine,
+    query: String,
+    limit: usize,
+    deep: bool,
+) -> Result<RecallResultSet> {
+    engine.recall(RecallRequest {
+        query,
+        limit,
+        deep,
+        include_related_records: false,
+    })
+}
+
+fn render_recall_output(
+    result: &RecallResultSet,
+    format: RecallFormat,
+    fields: Option<&FieldSet>,
+) -> Result<String> {
+    match format {
+        RecallFormat::Text => render_recall_result(result, false, fields),
+        RecallFormat::Json => render_recall_result(result, true, fields),
+        RecallFormat::Table => Ok(render_recall_table(result, fields)),
+        RecallFormat::Jsonl => {
+            let mut output = Vec::new();
+            write_recall_jsonl(result, fields, &mut output)?;
+            Ok(String::from_utf8(output)?.trim_end().to_string())
+        }
+    }
+}
+
+fn open_engine(profile: Option<&str>) -> Result<MemoryEngine> {
+    Ok(open_engine_with_data_dir(profile)?.0)
+}
+
+fn open_engine_with(
+    profile: Option<&str>,
+    adjust: impl FnOnce(&mut EngineConfig),
+) -> Result<MemoryEngine> {
+    let config_dir = default_config_dir()?;
+    let data_dir = resolve_data_dir_for_config_dir(&config_dir, profile)?;
+    let mut config = config::build_engine_config(&data_dir, &config_dir, profile)?;
+    adjust(&mut config);
+    MemoryEngine::open(config)
 }
 
-fn open_engine_with_data_dir() -> Result<(MemoryEngine, PathBuf)> {
+fn open_engine_with_data_dir(profile: Option<&str>) -> Result<(MemoryEngine, PathBuf)> {
     let config_dir = default_config_dir()?;
-    let data_dir = resolve_data_dir_for_config_dir(&config_dir)?;
-    let config = config::build_engine_config(&data_dir, &config_dir)?;
+    let data_dir = resolve_data_dir_for_config_dir(&config_dir, profile)?;
+    let config = config::build_engine_config(&data_dir, &config_dir, profile)?;
     Ok((MemoryEngine::open(config)?, data_dir))
 }
@@ -1,4 +1,6 @@
 pub(crate) mod args;
+pub(crate) mod bench;
 pub(crate) mod commands;
 pub(crate) mod output;
 pub(crate) mod paths;
+pub(crate) mod repl;
@@ -0,0 +1,94 @@
+use memo_engine::{GraphNode, MemoryGraph};
+
+use super::common::truncate_chars;
+
+const GRAPH_LABEL_CHARS: usize = 40;
+
+pub(crate) fn render_graph_dot(graph: &MemoryGraph) -> String {
+    let mut lines = vec!["graph memo {".to_string()];
+    for node in &graph.nodes {
+        lines.push(format!(
+            "  \"{}\" [label=\"{}\"];",
+            escape_dot(&node_key(node)),
+            escape_dot(&node_label(node)),
+        ));
+    }
+    for edge in &graph.edges {
+        lines.push(format!(
+            "  \"{}\" -- \"{}\" [weight={:.3}];",
+            escape_dot(&node_key(&graph.nodes[edge.source])),
+            escape_dot(&node_key(&graph.nodes[edge.target])),
+            edge.similarity,
+        ));
+    }
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+pub(crate) fn render_graph_graphml(graph: &MemoryGraph) -> String {
+    let mut lines = vec![
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string(),
+        r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#.to_string(),
+        r#"  <key id="label" for="node" attr.name="label" attr.type="string"/>"#.to_string(),
+        r#"  <key id="kind" for="node" attr.name="kind" attr.type="string"/>"#.to_string(),
+        r#"  <key id="weight" for="edge" attr.name="weight" attr.type="double"/>"#.to_string(),
+        r#"  <graph id="memo" edgedefault="undirected">"#.to_string(),
+    ];
+    for node in &graph.nodes {
+        lines.push(format!(
+            r#"    <node id="{}">"#,
+            escape_xml(&node_key(node))
+        ));
+        lines.push(format!(
+            r#"      <data key="label">{}</data>"#,
+            escape_xml(&node_label(node))
+        ));
+        lines.push(format!(
+            r#"      <data key="kind">{}</data>"#,
+            escape_xml(&node.kind)
+        ));
+        lines.push("    </node>".to_string());
+    }
+    for edge in &graph.edges {
+        lines.push(format!(
+            r#"    <edge source="{}" target="{}">"#,
+            escape_xml(&node_key(&graph.nodes[edge.source])),
+            escape_xml(&node_key(&graph.nodes[edge.target])),
+        ));
+        lines.push(format!(
+            r#"      <data key="weight">{:.3}</data>"#,
+            edge.similarity
+        ));
+        lines.push("    </edge>".to_string());
+    }
+    lines.push("  </graph>".to_string());
+    lines.push("</graphml>".to_string());
+    lines.join("\n")
+}
+
+fn node_key(node: &GraphNode) -> String {
+    format!("{}:{}", node.kind, node.id)
+}
+
+fn node_label(node: &GraphNode) -> String {
+    format!(
+        "{}\n{}",
+        node.id,
+        truncate_chars(&node.label, GRAPH_LABEL_CHARS)
+    )
+}
+
+fn escape_dot(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -1,12 +1,81 @@
+use std::{
+    collections::HashSet,
+    io::{ErrorKind, Write},
+};
+
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use memo_engine::{MemoryRecord, RecallReason, RecallResultSet};
+use memo_engine::{MemoryCluster, MemoryRecord, RecallReason, RecallResult, RecallResultSet};
+use serde_json::{Map, Value};
+
+use super::common::{render_json_or_text, truncate_chars};
+
+const CLUSTER_LABEL_CHARS: usize = 60;
+const TABLE_ID_CHARS: usize = 8;
+const TABLE_CONTENT_CHARS: usize = 60;
+/// Columns shown by `recall --format table` when `--fields` is not set.
+const DEFAULT_TABLE_FIELDS: &[&str] = &["score", "id", "updated_at", "layer", "content"];
+
+const RECALL_FIELDS: &[&str] = &[
+    "id",
+    "kind",
+    "content",
+    "score",
+    "layer",
+    "reasons",
+    "recency_score",
+    "updated_at",
+];
+
+/// Subset of per-result recall fields selected with `--fields`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FieldSet(HashSet<String>);
+
+impl FieldSet {
+    pub(crate) fn parse(raw: &str) -> Result<Self> {
+        let mut fields = HashSet::new();
+        for field in raw
+            .split(',')
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+        {
+            if !RECALL_FIELDS.contains(&field) {
+                anyhow::bail!(
+                    "unknown field `{field}`; expected one of: {}",
+                    RECALL_FIELDS.join(", ")
+                );
+            }
+            fields.insert(field.to_string());
+        }
+        if fields.is_empty() {
+            anyhow::bail!("--fields requires at least one field");
+        }
+        Ok(Self(fields))
+    }
 
-use super::common::render_json_or_text;
+    fn contains(&self, field: &str) -> bool {
+        self.0.contains(field)
+    }
+}
 
-pub(crate) fn render_recall_result(result: &RecallResultSet, json: bool) -> Result<String> {
+pub(crate) fn render_recall_result(
+    result: &RecallResultSet,
+    json: bool,
+    fields: Option<&FieldSet>,
+) -> Result<String> {
     if json {
-        return render_json_or_text(result, "", true);
+        let Some(fields) = fields else {
+            return render_json_or_text(result, "", true);
+        };
+        let mut value = serde_json::to_value(result)?;
+        value["results"] = Value::Array(
+            result
+                .results
+                .iter()
+                .map(|item| project_recall_result(item, fields))
+                .collect::<Result<_>>()?,
+        );
+        return Ok(serde_json::to_string_pretty(&value)?);
     }
 
     let mut lines = vec![format!(
@@ -31,25 +100,228 @@ pub(crate) fn render_recall_result(result: &RecallResultSet, json: bool) -> Resu
     ));
 
     for (index, item) in result.results.iter().enumerate() {
+        match fields {
+            Some(fields) => lines.extend(selected_recall_lines(index, item, fields)),
+            None => lines.extend(default_recall_lines(index, item)),
+        }
+    }
+
+    Ok(lines.join("\n"))
+}
+
+/// Writes one compact JSON object per recalled item, flushing after each line so pipelines
+/// see results as soon as they are written. A reader that exits early, as in `| head -1`,
+/// ends the output rather than failing the command.
+pub(crate) fn write_recall_jsonl(
+    result: &RecallResultSet,
+    fields: Option<&FieldSet>,
+    writer: &mut impl Write,
+) -> Result<()> {
+    for item in &result.results {
+        let line = match fields {
+            Some(fields) => serde_json::to_string(&project_recall_result(item, fields)?)?,
+            None => serde_json::to_string(item)?,
+        };
+        match writeln!(writer, "{line}").and_then(|()| writer.flush()) {
+            Err(error) if error.kind() == ErrorKind::BrokenPipe => return Ok(()),
+            written => written?,
+        }
+    }
+    Ok(())
+}
+
+/// Lays recall results out as a fixed-width table, one row per result. Ids are shortened to
+/// their first 8 characters and content to 60, so rows stay on one terminal line.
+pub(crate) fn render_recall_table(result: &RecallResultSet, fields: Option<&FieldSet>) -> String {
+    let columns = match fields {
+        Some(fields) => RECALL_FIELDS
+            .iter()
+            .copied()
+            .filter(|field| fields.contains(field))
+            .collect::<Vec<_>>(),
+        None => DEFAULT_TABLE_FIELDS.to_vec(),
+    };
+    let rows = result
+        .results
+        .iter()
+        .map(|item| {
+            columns
+                .iter()
+                .map(|column| table_cell(item, column))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let widths = columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            rows.iter()
+                .map(|row| row[index].chars().count())
+                .fold(column.len(), usize::max)
+        })
+        .collect::<Vec<_>>();
+
+    let headers = columns
+        .iter()
+        .map(|column| column.to_string())
+        .collect::<Vec<_>>();
+    let separator = widths
+        .iter()
+        .map(|width| "-".repeat(*width))
+        .collect::<Vec<_>>();
+    let mut lines = vec![table_row(&headers, &widths), table_row(&separator, &widths)];
+    lines.extend(rows.iter().map(|row| table_row(row, &widths)));
+    lines.join("\n")
+}
+
+fn table_row(cells: &[String], widths: &[usize]) -> String {
+    cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{cell:<width$}"))
+        .collect::<Vec<_>>()
+        .join("  ")
+        .trim_end()
+        .to_string()
+}
+
+fn table_cell(item: &RecallResult, column: &str) -> String {
+    match column {
+        "id" => item.memory.id().chars().take(TABLE_ID_CHARS).collect(),
+        "kind" => item.memory.kind().to_string(),
+        "content" => truncate_chars(&memory_summary(&item.memory), TABLE_CONTENT_CHARS),
+        "score" => format!("{:.3}", item.score),
+        "layer" => item.memory.layer().as_str().to_string(),
+        "reasons" => recall_reason_labels(&item.reasons),
+        "recency_score" => item
+            .recency_score
+            .map_or_else(|| "-".to_string(), |score| format!("{score:.3}")),
+        "updated_at" => item.memory.updated_at().format("%Y-%m-%d").to_string(),
+        _ => unreachable!("table columns come from RECALL_FIELDS"),
+    }
+}
+
+fn default_recall_lines(index: usize, item: &RecallResult) -> Vec<String> {
+    let mut header = format!(
+        "{}. [{}:{}] score={:.3} layer={}",
+        index + 1,
+        item.memory.kind(),
+        item.memory.id(),
+        item.score,
+        item.memory.layer().as_str(),
+    );
+    if let Some(recency_score) = item.recency_score {
+        header.push_str(&format!(" recency={recency_score:.3}"));
+    }
+    let mut lines = vec![header, format!("   {}", memory_summary(&item.memory))];
+    if !item.reasons.is_empty() {
         lines.push(format!(
-            "{}. [{}:{}] score={:.3} layer={}",
-            index + 1,
-            item.memory.kind(),
-            item.memory.id(),
-            item.score,
-            item.memory.layer().as_str(),
+            "   reasons: {}",
+            recall_reason_labels(&item.reasons)
         ));
+    }
+    lines
+}
+
+fn selected_recall_lines(index: usize, item: &RecallResult, fields: &FieldSet) -> Vec<String> {
+    let mut header = vec![format!("{}.", index + 1)];
+    match (fields.contains("kind"), fields.contains("id")) {
+        (true, true) => header.push(format!("[{}:{}]", item.memory.kind(), item.memory.id())),
+        (true, false) => header.push(format!("[{}]", item.memory.kind())),
+        (false, true) => header.push(format!("[{}]", item.memory.id())),
+        (false, false) => {}
+    }
+    if fields.contains("score") {
+        header.push(format!("score={:.3}", item.score));
+    }
+    if fields.contains("layer") {
+        header.push(format!("layer={}", item.memory.layer().as_str()));
+    }
+    if let Some(recency_score) = item
+        .recency_score
+        .filter(|_| fields.contains("recency_score"))
+    {
+        header.push(format!("recency={recency_score:.3}"));
+    }
+    if fields.contains("updated_at") {
+        header.push(format!(
+            "updated_at={}",
+            item.memory.updated_at().to_rfc3339()
+        ));
+    }
+
+    let mut lines = vec![header.join(" ")];
+    if fields.contains("content") {
         lines.push(format!("   {}", memory_summary(&item.memory)));
-        if !item.reasons.is_empty() {
-            lines.push(format!(
-                "   reasons: {}",
-                item.reasons
-                    .iter()
-                    .map(recall_reason_label)
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            ));
-        }
+    }
+    if fields.contains("reasons") && !item.reasons.is_empty() {
+        lines.push(format!(
+            "   reasons: {}",
+            recall_reason_labels(&item.reasons)
+        ));
+    }
+    lines
+}
+
+fn project_recall_result(item: &RecallResult, fields: &FieldSet) -> Result<Value> {
+    let mut object = Map::new();
+    for field in RECALL_FIELDS.iter().filter(|field| fields.contains(field)) {
+        let value = match *field {
+            "id" => Value::from(item.memory.id()),
+            "kind" => Value::from(item.memory.kind()),
+            "content" => Value::from(memory_summary(&item.memory)),
+            "score" => Value::from(item.score),
+            "layer" => Value::from(item.memory.layer().as_str()),
+            "reasons" => serde_json::to_value(&item.reasons)?,
+            "recency_score" => serde_json::to_value(item.recency_score)?,
+            "updated_at" => serde_json::to_value(item.memory.updated_at())?,
+            _ => unreachable!("RECALL_FIELDS only lists projected fields"),
+        };
+        object.insert(field.to_string(), value);
+    }
+    Ok(Value::Object(object))
+}
+
+fn recall_reason_labels(reasons: &[RecallReason]) -> String {
+    reasons
+        .iter()
+        .map(recall_reason_label)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub(crate) fn render_clusters(clusters: &[MemoryCluster], json: bool) -> Result<String> {
+    if json {
+        return render_json_or_text(&clusters, "", true);
+    }
+
+    let member_count = clusters
+        .iter()
+        .map(|cluster| cluster.members.len())
+        .sum::<usize>();
+    let mut lines = vec![format!(
+        "Found {} cluster(s) across {} vectorized memory record(s)",
+        clusters.len(),
+        member_count
+    )];
+    for (index, cluster) in clusters.iter().enumerate() {
+        lines.push(format!(
+            "{}. [{}:{}] {} ({} member(s))",
+            index + 1,
+            cluster.label_kind,
+            cluster.label_id,
+            truncate_chars(&cluster.label, CLUSTER_LABEL_CHARS),
+            cluster.members.len(),
+        ));
+        lines.push(format!(
+            "   members: {}",
+            cluster
+                .members
+                .iter()
+                .map(|member| format!("{}:{}", member.kind, member.id))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
     }
 
     Ok(lines.join("\n"))
@@ -1,21 +1,41 @@
 mod common;
+mod graph;
 mod memory;
 mod system;
 
 pub(crate) use common::render_json_or_text;
-pub(crate) use memory::{render_recall_result, render_reflection};
-pub(crate) use system::{render_awaken_result, render_dream_report, render_state};
+pub(crate) use graph::{render_graph_dot, render_graph_graphml};
+pub(crate) use memory::{
+    render_clusters, render_recall_result, render_recall_table, render_reflection,
+    write_recall_jsonl, FieldSet,
+};
+pub(crate) use system::{
+    render_audit_log, render_awaken_result, render_bench_report, render_config_validation,
+    render_doctor, render_dream_report, render_info, render_saved_recalls, render_state,
+    render_vacuum_report, BenchOperation, BenchReport, InfoReport,
+};
 
 #[cfg(test)]
 mod tests {
-    use super::{render_dream_report, render_recall_result, render_reflection, render_state};
+    use std::io::{self, Write};
+
+    use super::{
+        render_audit_log, render_bench_report, render_clusters, render_config_validation,
+        render_doctor, render_dream_report, render_graph_dot, render_graph_graphml, render_info,
+        render_recall_result, render_recall_table, render_reflection, render_saved_recalls,
+        render_state, render_vacuum_report, write_recall_jsonl, BenchOperation, BenchReport,
+        FieldSet, InfoReport,
+    };
+    use crate::config::{DoctorCheck, SavedRecall};
+    use crate::providers::health::ProviderCheck;
     use crate::providers::status::{
         ProviderCapabilityReadiness, ProviderCapabilityStatus, ProviderHealth, ProviderReadiness,
         ProviderReadinessSummary, ProviderRuntimeSummary,
     };
     use chrono::{TimeZone, Utc};
     use memo_engine::{
-        DreamProviderCallSummary, DreamReport, EpisodeRecord, FactRecord, IndexStatus, MemoryLayer,
+        AuditEntry, ClusterMember, DreamProviderCallSummary, DreamReport, EpisodeRecord,
+        FactRecord, GraphEdge, GraphNode, IndexStatus, MemoryCluster, MemoryGraph, MemoryLayer,
         MemoryRecord, RecallCapabilities, RecallReason, RecallResult, RecallResultSet, SystemState,
     };
 
@@ -215,9 +235,11 @@ mod tests {
                         RecallReason::LayerBoost,
                         RecallReason::Pinned,
                     ],
+                    recency_score: None,
                 }],
             },
             false,
+            None,
         )
         .expect("expected human recall output");
 
@@ -263,9 +285,11 @@ mod tests {
                     }),
                     score: 3.5,
                     reasons: vec![RecallReason::SessionCache],
+                    recency_score: None,
                 }],
             },
             false,
+            None,
         )
         .expect("expected human recall output");
 
@@ -305,9 +329,11 @@ mod tests {
                     }),
                     score: 1.0,
                     reasons: vec![RecallReason::Pinned],
+                    recency_score: None,
                 }],
             },
             true,
+            None,
         )
         .expect("expected json recall output");
 
@@ -321,6 +347,193 @@ mod tests {
         assert_eq!(parsed["results"][0]["reasons"][0], "pinned");
     }
 
+    #[test]
+    fn render_recall_with_fields_keeps_only_selected_keys() {
+        let result = RecallResultSet {
+            total_candidates: 1,
+            provider_calls: 0,
+            capabilities: RecallCapabilities {
+                text: true,
+                vector: false,
+                l1: true,
+                l2: false,
+                l3: false,
+                working_set: false,
+            },
+            deep_search_used: false,
+            results: vec![RecallResult {
+                score: 2.5,
+                ..episode_result("ep-1")
+            }],
+        };
+        let fields = FieldSet::parse("id,score").expect("expected valid fields");
+
+        let text = render_recall_result(&result, false, Some(&fields))
+            .expect("expected human recall output");
+        let json = render_recall_result(&result, true, Some(&fields))
+            .expect("expected json recall output");
+
+        assert!(text.contains("1. [ep-1] score=2.500"));
+        assert!(!text.contains("layer="));
+        assert!(!text.contains("Alice lives in Paris."));
+        assert!(!text.contains("reasons:"));
+        let parsed: serde_json::Value =
+            serde_json::from_str(&json).expect("expected valid json output");
+        assert_eq!(parsed["total_candidates"], 1);
+        assert_eq!(
+            parsed["results"][0],
+            serde_json::json!({ "id": "ep-1", "score": 2.5 })
+        );
+    }
+
+    #[test]
+    fn render_recall_table_aligns_truncated_columns() {
+        let mut long = RecallResult {
+            score: 1.25,
+            ..episode_result("ep-2")
+        };
+        if let MemoryRecord::Episode(episode) = &mut long.memory {
+            episode.content = "long ".repeat(20);
+        }
+        let result = RecallResultSet {
+            total_candidates: 2,
+            provider_calls: 0,
+            capabilities: RecallCapabilities {
+                text: true,
+                vector: false,
+                l1: true,
+                l2: false,
+                l3: false,
+                working_set: false,
+            },
+            deep_search_used: false,
+            results: vec![
+                RecallResult {
+                    score: 2.5,
+                    ..episode_result("0193f1c2-aaaa-bbbb")
+                },
+                long,
+            ],
+        };
+
+        let table = render_recall_table(&result, None);
+        let lines = table.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("score  id        updated_at  layer  content"));
+        assert!(lines[1].starts_with("-----  --------  ----------  -----  -------"));
+        assert_eq!(
+            lines[2],
+            "2.500  0193f1c2  2026-04-21  L1     Alice lives in Paris."
+        );
+        assert!(lines[3].starts_with("1.250  ep-2      2026-04-21  L1     long long"));
+        assert!(lines[3].ends_with('…'));
+
+        let fields = FieldSet::parse("id,reasons").expect("expected valid fields");
+        let narrow = render_recall_table(&result, Some(&fields));
+        assert_eq!(narrow.lines().next(), Some("id        reasons"));
+        assert!(narrow.contains("0193f1c2  pinned"));
+    }
+
+    #[test]
+    fn write_recall_jsonl_emits_one_compact_object_per_result() {
+        let mut output = Vec::new();
+
+        write_recall_jsonl(
+            &RecallResultSet {
+                total_candidates: 2,
+                provider_calls: 0,
+                capabilities: RecallCapabilities {
+                    text: true,
+                    vector: false,
+                    l1: true,
+                    l2: false,
+                    l3: false,
+                    working_set: false,
+                },
+                deep_search_used: false,
+                results: vec![episode_result("ep-1"), episode_result("ep-2")],
+            },
+            None,
+            &mut output,
+        )
+        .expect("expected jsonl recall output");
+
+        let output = String::from_utf8(output).expect("expected utf-8 output");
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        let first: serde_json::Value =
+            serde_json::from_str(lines[0]).expect("expected one json object per line");
+        assert_eq!(first["memory"]["Episode"]["id"], "ep-1");
+        assert_eq!(first["reasons"][0], "pinned");
+        assert!(!output.contains("total_candidates"));
+    }
+
+    fn episode_result(id: &str) -> RecallResult {
+        RecallResult {
+            memory: MemoryRecord::Episode(EpisodeRecord {
+                id: id.to_string(),
+                content: "Alice lives in Paris.".to_string(),
+                layer: MemoryLayer::L1,
+                confidence: 0.9,
+                source_episode_id: None,
+                session_id: None,
+                created_at: Utc.with_ymd_and_hms(2026, 4, 21, 10, 0, 0).unwrap(),
+                updated_at: Utc.with_ymd_and_hms(2026, 4, 21, 10, 0, 0).unwrap(),
+                last_seen_at: Utc.with_ymd_and_hms(2026, 4, 21, 10, 0, 0).unwrap(),
+                archived_at: None,
+                invalidated_at: None,
+                hit_count: 0,
+            }),
+            score: 1.0,
+            reasons: vec![RecallReason::Pinned],
+            recency_score: None,
+        }
+    }
+
+    #[test]
+    fn write_recall_jsonl_stops_quietly_when_the_reader_goes_away() {
+        struct ClosesAfterFirstLine(Vec<u8>);
+
+        impl Write for ClosesAfterFirstLine {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                if self.0.contains(&b'\n') {
+                    return Err(io::Error::from(io::ErrorKind::BrokenPipe));
+                }
+                self.0.write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+        let mut output = ClosesAfterFirstLine(Vec::new());
+
+        write_recall_jsonl(
+            &RecallResultSet {
+                total_candidates: 2,
+                provider_calls: 0,
+                capabilities: RecallCapabilities {
+                    text: true,
+                    vector: false,
+                    l1: true,
+                    l2: false,
+                    l3: false,
+                    working_set: false,
+                },
+                deep_search_used: false,
+                results: vec![episode_result("ep-1"), episode_result("ep-2")],
+            },
+            None,
+            &mut output,
+        )
+        .expect("expected a closed pipe to end the output without an error");
+
+        let output = String::from_utf8(output.0).expect("expected utf-8 output");
+        assert_eq!(output.lines().count(), 1);
+        assert!(output.contains("ep-1"));
+    }
+
     #[test]
     fn render_reflection_marks_archived_episode_status() {
         let output = render_reflection(
@@ -529,4 +742,258 @@ mod tests {
         assert_eq!(parsed["dream"]["provider_calls"]["extraction_calls"], 1);
         assert_eq!(parsed["dream"]["provider_calls"]["embedding_calls"], 3);
     }
+
+    #[test]
+    fn render_bench_report_aligns_latency_columns() {
+        let report = BenchReport {
+            operations: vec![BenchOperation {
+                operation: "remember",
+                count: 100,
+                p50_ms: 1.25,
+                p95_ms: 2.5,
+                p99_ms: 4.0,
+                total_ms: 150.0,
+            }],
+        };
+
+        let text = render_bench_report(&report, false).expect("expected bench table");
+        let json = render_bench_report(&report, true).expect("expected bench json");
+
+        assert_eq!(
+            text,
+            "operation  count    p50_ms    p95_ms    p99_ms   total_ms\nremember     100     1.250     2.500     4.000    150.000"
+        );
+        let parsed: serde_json::Value =
+            serde_json::from_str(&json).expect("expected valid json output");
+        assert_eq!(parsed["operations"][0]["operation"], "remember");
+        assert_eq!(parsed["operations"][0]["count"], 100);
+    }
+
+    #[test]
+    fn render_vacuum_report_reports_freed_bytes() {
+        let data_dir = std::path::Path::new("/tmp/memo");
+
+        let text = render_vacuum_report(data_dir, 4096, 1024, false).expect("expected text");
+        let json = render_vacuum_report(data_dir, 1024, 2048, true).expect("expected json");
+
+        assert_eq!(
+            text,
+            "Vacuumed /tmp/memo\nbefore: 4.0 KiB\nafter: 1.0 KiB\nfreed: 3.0 KiB"
+        );
+        let parsed: serde_json::Value =
+            serde_json::from_str(&json).expect("expected valid json output");
+        assert_eq!(parsed["freed_bytes"], 0);
+        assert_eq!(parsed["after_bytes"], 2048);
+    }
+
+    #[test]
+    fn render_saved_recalls_lists_query_and_options() {
+        let output = render_saved_recalls(
+            &[
+                SavedRecall {
+                    name: "alice".to_string(),
+                    query: "where does Alice live".to_string(),
+                    limit: 5,
+                    deep: true,
+                    boost_recent: false,
+                },
+                SavedRecall {
+                    name: "travel".to_string(),
+                    query: "Paris".to_string(),
+                    limit: 10,
+                    deep: false,
+                    boost_recent: true,
+                },
+            ],
+            false,
+        )
+        .expect("expected human saved recall output");
+
+        assert_eq!(
+            output,
+            "alice   \"where does Alice live\"  limit=5 deep\ntravel  \"Paris\"  limit=10 boost_recent"
+        );
+    }
+
+    #[test]
+    fn render_info_summarizes_paths_records_and_vector_index() {
+        let report = InfoReport {
+            version: "0.2.0",
+            config_dir: "/home/alice/.memo".into(),
+            data_dir: "/home/alice/.memo/data".into(),
+            data_size_bytes: 3 * 1024 * 1024 / 2,
+            episode_count: 3,
+            entity_count: 2,
+            fact_count: 1,
+            edge_count: 0,
+            embedding_provider: Some("openai.embed".to_string()),
+            vector_dimension: 1536,
+            vector_index_exists: true,
+            vector_documents: 6,
+        };
+
+        let output = render_info(&report, false).expect("expected human info output");
+
+        assert_eq!(
+            output,
+            "memo 0.2.0\nconfig_dir: /home/alice/.memo\ndata_dir: /home/alice/.memo/data (1.5 MiB)\nrecords: episodes=3 entities=2 facts=1 edges=0\nembedding_provider: openai.embed (dimension 1536)\nvector_index: present, 6 document(s)"
+        );
+        let json = render_info(&report, true).expect("expected json info output");
+        let parsed: serde_json::Value =
+            serde_json::from_str(&json).expect("expected valid json output");
+        assert_eq!(parsed["vector_dimension"], 1536);
+        assert_eq!(parsed["data_size_bytes"], 1572864);
+    }
+
+    #[test]
+    fn render_audit_log_prints_one_line_per_entry() {
+        let output = render_audit_log(
+            &[
+                AuditEntry {
+                    timestamp: Utc.with_ymd_and_hms(2026, 4, 21, 10, 0, 0).unwrap(),
+                    operation: "remember".to_string(),
+                    ids: vec!["ep-1".to_string()],
+                    pid: 42,
+                    user: Some("alice".to_string()),
+                },
+                AuditEntry {
+                    timestamp: Utc.with_ymd_and_hms(2026, 4, 21, 11, 0, 0).unwrap(),
+                    operation: "dream".to_string(),
+                    ids: Vec::new(),
+                    pid: 43,
+                    user: None,
+                },
+            ],
+            false,
+        )
+        .expect("expected human audit output");
+
+        assert_eq!(
+            output,
+            "2026-04-21T10:00:00+00:00 remember pid=42 user=alice ids=ep-1\n2026-04-21T11:00:00+00:00 dream pid=43"
+        );
+    }
+
+    #[test]
+    fn render_doctor_marks_each_check_and_aligns_details() {
+        let output = render_doctor(
+            &[
+                DoctorCheck {
+                    name: "config.toml".to_string(),
+                    ok: true,
+                    detail: None,
+                },
+                DoctorCheck {
+                    name: "data directory".to_string(),
+                    ok: false,
+                    detail: Some("/tmp/data is not writable".to_string()),
+                },
+            ],
+            false,
+        )
+        .expect("expected human doctor output");
+
+        assert_eq!(
+            output,
+            "✓ config.toml\n✗ data directory  /tmp/data is not writable"
+        );
+    }
+
+    #[test]
+    fn render_config_validation_marks_each_service_with_latency_or_error() {
+        let output = render_config_validation(
+            &[
+                ProviderCheck {
+                    capability: "embedding".to_string(),
+                    provider_ref: "openai.embed".to_string(),
+                    ok: true,
+                    latency_ms: Some(120),
+                    detail: None,
+                },
+                ProviderCheck {
+                    capability: "rerank".to_string(),
+                    provider_ref: "aliyun.rerank".to_string(),
+                    ok: false,
+                    latency_ms: None,
+                    detail: Some("connection refused".to_string()),
+                },
+            ],
+            false,
+        )
+        .expect("expected human validation output");
+
+        assert_eq!(
+            output,
+            "✓ embedding  openai.embed   120ms\n✗ rerank     aliyun.rerank  connection refused"
+        );
+    }
+
+    #[test]
+    fn render_clusters_lists_label_and_members() {
+        let output = render_clusters(
+            &[MemoryCluster {
+                label_kind: "episode".to_string(),
+                label_id: "ep-1".to_string(),
+                label: "Paris travel checklist for May.".to_string(),
+                members: vec![
+                    ClusterMember {
+                        kind: "episode".to_string(),
+                        id: "ep-1".to_string(),
+                    },
+                    ClusterMember {
+                        kind: "fact".to_string(),
+                        id: "fact-1".to_string(),
+                    },
+                ],
+            }],
+            false,
+        )
+        .expect("expected human cluster output");
+
+        assert!(output.starts_with("Found 1 cluster(s) across 2 vectorized memory record(s)"));
+        assert!(output.contains("1. [episode:ep-1] Paris travel checklist for May. (2 member(s))"));
+        assert!(output.contains("members: episode:ep-1, fact:fact-1"));
+    }
+
+    fn sample_graph() -> MemoryGraph {
+        MemoryGraph {
+            nodes: vec![
+                GraphNode {
+                    kind: "episode".to_string(),
+                    id: "ep-1".to_string(),
+                    label: "Alice said \"Paris\" & <Lyon>".to_string(),
+                },
+                GraphNode {
+                    kind: "fact".to_string(),
+                    id: "fact-1".to_string(),
+                    label: "Alice lives in Paris".to_string(),
+                },
+            ],
+            edges: vec![GraphEdge {
+                source: 0,
+                target: 1,
+                similarity: 0.912,
+            }],
+        }
+    }
+
+    #[test]
+    fn render_graph_dot_escapes_labels_and_weights_edges() {
+        let output = render_graph_dot(&sample_graph());
+
+        assert!(output.starts_with("graph memo {"));
+        assert!(output.contains(r#""episode:ep-1" [label="ep-1\nAlice said \"Paris\" & <Lyon>"];"#));
+        assert!(output.contains(r#""episode:ep-1" -- "fact:fact-1" [weight=0.912];"#));
+        assert!(output.ends_with('}'));
+    }
+
+    #[test]
+    fn render_graph_graphml_escapes_xml_entities() {
+        let output = render_graph_graphml(&sample_graph());
+
+        assert!(output.contains(r#"<node id="episode:ep-1">"#));
+        assert!(output.contains("Alice said &quot;Paris&quot; &amp; &lt;Lyon&gt;"));
+        assert!(output.contains(r#"<edge source="episode:ep-1" target="fact:fact-1">"#));
+        assert!(output.contains(r#"<data key="weight">0.912</data>"#));
+    }
 }
@@ -1,12 +1,15 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
-use memo_engine::{DreamReport, IndexStatus, SystemState};
+use memo_engine::{AuditEntry, DreamReport, IndexStatus, SystemState};
 use serde::Serialize;
 
-use crate::{config, providers::status};
+use crate::{
+    config::{self, DoctorCheck, SavedRecall},
+    providers::{health::ProviderCheck, status},
+};
 
-use super::common::{created_label, render_json_or_text};
+use super::common::{created_label, format_bytes, render_json_or_text};
 
 pub(crate) fn render_awaken_result(
     data_dir: &Path,
@@ -22,6 +25,48 @@ pub(crate) fn render_awaken_result(
     )
 }
 
+#[derive(Debug, Serialize)]
+pub(crate) struct InfoReport {
+    pub version: &'static str,
+    pub config_dir: PathBuf,
+    pub data_dir: PathBuf,
+    pub data_size_bytes: u64,
+    pub episode_count: usize,
+    pub entity_count: usize,
+    pub fact_count: usize,
+    pub edge_count: usize,
+    pub embedding_provider: Option<String>,
+    pub vector_dimension: usize,
+    pub vector_index_exists: bool,
+    pub vector_documents: usize,
+}
+
+pub(crate) fn render_info(report: &InfoReport, json: bool) -> Result<String> {
+    let embedding = match report.embedding_provider.as_deref() {
+        Some(provider_ref) => format!("{provider_ref} (dimension {})", report.vector_dimension),
+        None => "none".to_string(),
+    };
+    let vector_index = if report.vector_index_exists {
+        format!("present, {} document(s)", report.vector_documents)
+    } else {
+        "missing".to_string()
+    };
+    let human = format!(
+        "memo {}\nconfig_dir: {}\ndata_dir: {} ({})\nrecords: episodes={} entities={} facts={} edges={}\nembedding_provider: {}\nvector_index: {}",
+        report.version,
+        report.config_dir.display(),
+        report.data_dir.display(),
+        format_bytes(report.data_size_bytes),
+        report.episode_count,
+        report.entity_count,
+        report.fact_count,
+        report.edge_count,
+        embedding,
+        vector_index,
+    );
+    render_json_or_text(report, &human, json)
+}
+
 pub(crate) fn render_dream_report(report: &DreamReport, full: bool, json: bool) -> Result<String> {
     if json {
         let payload = serde_json::json!({
@@ -66,6 +111,205 @@ pub(crate) fn render_dream_report(report: &DreamReport, full: bool, json: bool)
     Ok(output)
 }
 
+pub(crate) fn render_config_validation(checks: &[ProviderCheck], json: bool) -> Result<String> {
+    if json {
+        let payload = serde_json::json!({
+            "ok": checks.iter().all(|check| check.ok),
+            "checks": checks,
+        });
+        return render_json_or_text(&payload, "", true);
+    }
+
+    if checks.is_empty() {
+        return Ok("no providers configured".to_string());
+    }
+
+    let capability_width = checks
+        .iter()
+        .map(|check| check.capability.len())
+        .max()
+        .unwrap_or_default();
+    let provider_width = checks
+        .iter()
+        .map(|check| check.provider_ref.len())
+        .max()
+        .unwrap_or_default();
+    let lines = checks
+        .iter()
+        .map(|check| {
+            let outcome = match (check.ok, check.latency_ms, check.detail.as_deref()) {
+                (true, Some(latency_ms), _) => format!("{latency_ms}ms"),
+                (_, _, Some(detail)) => detail.to_string(),
+                _ => String::new(),
+            };
+            format!(
+                "{} {:<capability_width$}  {:<provider_width$}  {}",
+                if check.ok { "✓" } else { "✗" },
+                check.capability,
+                check.provider_ref,
+                outcome,
+            )
+            .trim_end()
+            .to_string()
+        })
+        .collect::<Vec<_>>();
+    Ok(lines.join("\n"))
+}
+
+pub(crate) fn render_doctor(checks: &[DoctorCheck], json: bool) -> Result<String> {
+    if json {
+        let payload = serde_json::json!({
+            "ok": checks.iter().all(|check| check.ok),
+            "checks": checks,
+        });
+        return render_json_or_text(&payload, "", true);
+    }
+
+    let name_width = checks
+        .iter()
+        .map(|check| check.name.len())
+        .max()
+        .unwrap_or_default();
+    let lines = checks
+        .iter()
+        .map(|check| {
+            format!(
+                "{} {:<name_width$}  {}",
+                if check.ok { "✓" } else { "✗" },
+                check.name,
+                check.detail.as_deref().unwrap_or_default(),
+            )
+            .trim_end()
+            .to_string()
+        })
+        .collect::<Vec<_>>();
+    Ok(lines.join("\n"))
+}
+
+pub(crate) fn render_audit_log(entries: &[AuditEntry], json: bool) -> Result<String> {
+    if json {
+        return render_json_or_text(&entries, "", true);
+    }
+    if entries.is_empty() {
+        return Ok("no audit entries".to_string());
+    }
+
+    let lines = entries
+        .iter()
+        .map(|entry| {
+            let mut line = format!(
+                "{} {} pid={}",
+                entry.timestamp.to_rfc3339(),
+                entry.operation,
+                entry.pid
+            );
+            if let Some(user) = entry.user.as_deref() {
+                line.push_str(&format!(" user={user}"));
+            }
+            if !entry.ids.is_empty() {
+                line.push_str(&format!(" ids={}", entry.ids.join(",")));
+            }
+            line
+        })
+        .collect::<Vec<_>>();
+    Ok(lines.join("\n"))
+}
+
+pub(crate) fn render_vacuum_report(
+    data_dir: &Path,
+    before_bytes: u64,
+    after_bytes: u64,
+    json: bool,
+) -> Result<String> {
+    let freed_bytes = before_bytes.saturating_sub(after_bytes);
+    let payload = serde_json::json!({
+        "data_dir": data_dir,
+        "before_bytes": before_bytes,
+        "after_bytes": after_bytes,
+        "freed_bytes": freed_bytes,
+    });
+    let human = format!(
+        "Vacuumed {}\nbefore: {}\nafter: {}\nfreed: {}",
+        data_dir.display(),
+        format_bytes(before_bytes),
+        format_bytes(after_bytes),
+        format_bytes(freed_bytes),
+    );
+    render_json_or_text(&payload, &human, json)
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct BenchOperation {
+    pub operation: &'static str,
+    pub count: usize,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub total_ms: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct BenchReport {
+    pub operations: Vec<BenchOperation>,
+}
+
+pub(crate) fn render_bench_report(report: &BenchReport, json: bool) -> Result<String> {
+    if json {
+        return render_json_or_text(report, "", true);
+    }
+
+    let mut lines = vec![format!(
+        "{:<9} {:>6} {:>9} {:>9} {:>9} {:>10}",
+        "operation", "count", "p50_ms", "p95_ms", "p99_ms", "total_ms"
+    )];
+    for operation in &report.operations {
+        lines.push(format!(
+            "{:<9} {:>6} {:>9.3} {:>9.3} {:>9.3} {:>10.3}",
+            operation.operation,
+            operation.count,
+            operation.p50_ms,
+            operation.p95_ms,
+            operation.p99_ms,
+            operation.total_ms,
+        ));
+    }
+    Ok(lines.join("\n"))
+}
+
+pub(crate) fn render_saved_recalls(recalls: &[SavedRecall], json: bool) -> Result<String> {
+    if json {
+        return render_json_or_text(&recalls, "", true);
+    }
+    if recalls.is_empty() {
+        return Ok("no saved recalls".to_string());
+    }
+
+    let name_width = recalls
+        .iter()
+        .map(|recall| recall.name.len())
+        .max()
+        .unwrap_or_default();
+    let lines = recalls
+        .iter()
+        .map(|recall| {
+            let mut options = vec![format!("limit={}", recall.limit)];
+            if recall.deep {
+                options.push("deep".to_string());
+            }
+            if recall.boost_recent {
+                options.push("boost_recent".to_string());
+            }
+            format!(
+                "{:<name_width$}  {:?}  {}",
+                recall.name,
+                recall.query,
+                options.join(" ")
+            )
+        })
+        .collect::<Vec<_>>();
+    Ok(lines.join("\n"))
+}
+
 pub(crate) fn render_state(
     state: &SystemState,
     provider_runtime: &status::ProviderRuntimeSummary,
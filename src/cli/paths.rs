@@ -1,20 +1,42 @@
-use std::path::{Path, PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use crate::config;
-use anyhow::Result;
+use anyhow::{Context, Result};
 
 const MEMO_DATA_DIR_ENV: &str = "MEMO_DATA_DIR";
+const MEMO_PROFILE_ENV: &str = "MEMO_PROFILE";
 
 pub(crate) fn default_config_dir() -> Result<PathBuf> {
     Ok(user_home_dir()?.join(".memo"))
 }
 
-pub(crate) fn resolve_data_dir_for_config_dir(config_dir: &Path) -> Result<PathBuf> {
-    if let Some(value) = std::env::var_os(MEMO_DATA_DIR_ENV) {
-        return Ok(resolve_relative_to_dir(config_dir, Path::new(&value)));
+/// Config profile selected by `--profile`, falling back to `MEMO_PROFILE`.
+pub(crate) fn resolve_profile(flag: Option<String>) -> Option<String> {
+    flag.or_else(|| std::env::var(MEMO_PROFILE_ENV).ok())
+        .filter(|profile| !profile.is_empty())
+}
+
+pub(crate) fn resolve_data_dir_for_config_dir(
+    config_dir: &Path,
+    profile: Option<&str>,
+) -> Result<PathBuf> {
+    let env_data_dir = std::env::var_os(MEMO_DATA_DIR_ENV).map(PathBuf::from);
+    resolve_data_dir(config_dir, env_data_dir.as_deref(), profile)
+}
+
+fn resolve_data_dir(
+    config_dir: &Path,
+    env_data_dir: Option<&Path>,
+    profile: Option<&str>,
+) -> Result<PathBuf> {
+    if let Some(value) = env_data_dir {
+        return Ok(resolve_relative_to_dir(config_dir, value));
     }
 
-    if let Some(data_dir) = config::resolve_configured_data_dir(config_dir)? {
+    if let Some(data_dir) = config::resolve_configured_data_dir(config_dir, profile)? {
         return Ok(data_dir);
     }
 
@@ -42,6 +64,27 @@ fn user_home_dir() -> Result<PathBuf> {
     anyhow::bail!("failed to determine user home directory")
 }
 
+/// Total size in bytes of every regular file under `path`; a missing directory counts as empty.
+pub(crate) fn directory_size(path: &Path) -> Result<u64> {
+    if !path.exists() {
+        return Ok(0);
+    }
+
+    let mut total = 0;
+    for entry in fs::read_dir(path)
+        .with_context(|| format!("failed to read directory: {}", path.display()))?
+    {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            total += directory_size(&entry.path())?;
+        } else if metadata.is_file() {
+            total += metadata.len();
+        }
+    }
+    Ok(total)
+}
+
 pub(crate) fn resolve_relative_to_dir(base_dir: &Path, path: &Path) -> PathBuf {
     if path.is_absolute() {
         path.to_path_buf()
@@ -52,18 +95,18 @@ pub(crate) fn resolve_relative_to_dir(base_dir: &Path, path: &Path) -> PathBuf {
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
+    use std::{fs, path::Path};
 
     use tempfile::TempDir;
 
-    use super::resolve_data_dir_for_config_dir;
+    use super::{directory_size, resolve_data_dir};
 
     #[test]
     fn resolve_data_dir_defaults_to_user_config_data_subdir() -> anyhow::Result<()> {
         let temp = TempDir::new()?;
         let config_dir = temp.path().join(".memo");
 
-        let resolved = resolve_data_dir_for_config_dir(&config_dir)?;
+        let resolved = resolve_data_dir(&config_dir, None, None)?;
 
         assert_eq!(resolved, config_dir.join("data"));
         Ok(())
@@ -76,12 +119,15 @@ mod tests {
         fs::create_dir_all(&config_dir)?;
         fs::write(
             config_dir.join("config.toml"),
-            "[storage]\ndata_dir = \"memory-data\"\n",
+            "[storage]\ndata_dir = \"memory-data\"\n\
+             [profiles.scratch.storage]\ndata_dir = \"scratch-data\"\n",
         )?;
 
-        let resolved = resolve_data_dir_for_config_dir(&config_dir)?;
+        let resolved = resolve_data_dir(&config_dir, None, None)?;
+        let scratch = resolve_data_dir(&config_dir, None, Some("scratch"))?;
 
         assert_eq!(resolved, config_dir.join("memory-data"));
+        assert_eq!(scratch, config_dir.join("scratch-data"));
         Ok(())
     }
 
@@ -94,16 +140,22 @@ mod tests {
             config_dir.join("config.toml"),
             "[storage]\ndata_dir = \"memory-data\"\n",
         )?;
-        unsafe {
-            std::env::set_var("MEMO_DATA_DIR", "env-store");
-        }
 
-        let resolved = resolve_data_dir_for_config_dir(&config_dir)?;
+        let resolved = resolve_data_dir(&config_dir, Some(Path::new("env-store")), None)?;
 
-        unsafe {
-            std::env::remove_var("MEMO_DATA_DIR");
-        }
         assert_eq!(resolved, config_dir.join("env-store"));
         Ok(())
     }
+
+    #[test]
+    fn directory_size_sums_nested_files() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        fs::create_dir_all(temp.path().join("text-index"))?;
+        fs::write(temp.path().join("memory.db"), [0_u8; 10])?;
+        fs::write(temp.path().join("text-index").join("segment"), [0_u8; 5])?;
+
+        assert_eq!(directory_size(temp.path())?, 15);
+        assert_eq!(directory_size(&temp.path().join("missing"))?, 0);
+        Ok(())
+    }
 }
@@ -0,0 +1,197 @@
+use std::{io::Write, path::Path};
+
+use anyhow::{Context, Result};
+use rustyline::{error::ReadlineError, DefaultEditor};
+use tracing::warn;
+
+const PROMPT: &str = "recall> ";
+const EXIT_COMMANDS: &[&str] = &["exit", "quit"];
+
+/// Where the REPL reads queries from: a `rustyline` editor in the CLI, a fixed script in tests.
+pub(crate) trait QueryInput {
+    /// Returns the next line, or `None` once the user ends the session.
+    fn read_query(&mut self, prompt: &str) -> Result<Option<String>>;
+    fn add_history(&mut self, query: &str) -> Result<()>;
+    /// Appends the entries added since the last save to `path`.
+    fn save_history(&mut self, path: &Path) -> Result<()>;
+}
+
+impl QueryInput for DefaultEditor {
+    fn read_query(&mut self, prompt: &str) -> Result<Option<String>> {
+        match self.readline(prompt) {
+            Ok(line) => Ok(Some(line)),
+            Err(ReadlineError::Interrupted | ReadlineError::Eof) => Ok(None),
+            Err(error) => Err(error).context("failed to read recall query"),
+        }
+    }
+
+    fn add_history(&mut self, query: &str) -> Result<()> {
+        self.add_history_entry(query)
+            .context("failed to add recall history entry")?;
+        Ok(())
+    }
+
+    fn save_history(&mut self, path: &Path) -> Result<()> {
+        self.append_history(path)
+            .with_context(|| format!("failed to write recall history: {}", path.display()))
+    }
+}
+
+/// Opens a line editor with the saved history of `history_path` loaded, so earlier queries
+/// are one up-arrow away. An unreadable history file is logged and the session starts empty.
+pub(crate) fn open_query_editor(history_path: Option<&Path>) -> Result<DefaultEditor> {
+    let mut editor = DefaultEditor::new().context("failed to start line editor")?;
+    if let Some(path) = history_path.filter(|path| path.exists()) {
+        if let Err(error) = editor.load_history(path) {
+            warn!(path = %path.display(), error = %error, "failed to load recall history");
+        }
+    }
+    Ok(editor)
+}
+
+/// Reads one query per line until EOF, Ctrl-C, or `exit`, writing whatever `handle` renders
+/// for it. A failing query or history write prints its error and keeps the loop running.
+pub(crate) fn run_recall_repl(
+    input: &mut impl QueryInput,
+    output: &mut impl Write,
+    history_path: Option<&Path>,
+    mut handle: impl FnMut(&str) -> Result<String>,
+) -> Result<()> {
+    while let Some(line) = input.read_query(PROMPT)? {
+        let query = line.trim();
+        if query.is_empty() {
+            continue;
+        }
+        if EXIT_COMMANDS.contains(&query) {
+            return Ok(());
+        }
+        let recorded = input.add_history(query).and_then(|()| match history_path {
+            Some(path) => input.save_history(path),
+            None => Ok(()),
+        });
+        if let Err(error) = recorded {
+            writeln!(output, "warning: {error:#}")?;
+        }
+
+        match handle(query) {
+            Ok(rendered) => writeln!(output, "{rendered}")?,
+            Err(error) => writeln!(output, "error: {error:#}")?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::VecDeque, path::Path};
+
+    use anyhow::Result;
+
+    use super::{run_recall_repl, QueryInput};
+
+    #[derive(Default)]
+    struct ScriptedInput {
+        lines: VecDeque<&'static str>,
+        history: Vec<String>,
+        saved: Vec<String>,
+        fail_save: bool,
+    }
+
+    impl ScriptedInput {
+        fn new(lines: &[&'static str]) -> Self {
+            Self {
+                lines: lines.iter().copied().collect(),
+                ..Self::default()
+            }
+        }
+    }
+
+    impl QueryInput for ScriptedInput {
+        fn read_query(&mut self, _prompt: &str) -> Result<Option<String>> {
+            Ok(self.lines.pop_front().map(str::to_string))
+        }
+
+        fn add_history(&mut self, query: &str) -> Result<()> {
+            self.history.push(query.to_string());
+            Ok(())
+        }
+
+        fn save_history(&mut self, _path: &Path) -> Result<()> {
+            if self.fail_save {
+                anyhow::bail!("history is read-only");
+            }
+            self.saved = self.history.clone();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn repl_runs_each_query_until_exit_and_records_history() -> Result<()> {
+        let mut input = ScriptedInput::new(&["Alice", "", "  Paris  ", "exit", "ignored"]);
+        let mut output = Vec::new();
+        let mut seen = Vec::new();
+
+        run_recall_repl(
+            &mut input,
+            &mut output,
+            Some(Path::new("history")),
+            |query| {
+                seen.push(query.to_string());
+                Ok(format!("results for {query}"))
+            },
+        )?;
+
+        assert_eq!(seen, vec!["Alice", "Paris"]);
+        let output = String::from_utf8(output)?;
+        assert!(output.contains("results for Alice"));
+        assert!(output.contains("results for Paris"));
+        assert_eq!(input.saved, vec!["Alice", "Paris"]);
+        assert_eq!(input.lines, vec!["ignored"]);
+        Ok(())
+    }
+
+    #[test]
+    fn repl_reports_query_errors_and_continues_until_eof() -> Result<()> {
+        let mut input = ScriptedInput::new(&["bad", "good"]);
+        let mut output = Vec::new();
+
+        run_recall_repl(&mut input, &mut output, None, |query| {
+            if query == "bad" {
+                anyhow::bail!("query failed");
+            }
+            Ok("ok".to_string())
+        })?;
+
+        let output = String::from_utf8(output)?;
+        assert!(output.contains("error: query failed"));
+        assert!(output.contains("ok"));
+        assert_eq!(input.history, vec!["bad", "good"]);
+        assert!(input.saved.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn repl_warns_and_continues_when_history_cannot_be_saved() -> Result<()> {
+        let mut input = ScriptedInput {
+            fail_save: true,
+            ..ScriptedInput::new(&["Alice", "Paris"])
+        };
+        let mut output = Vec::new();
+        let mut seen = Vec::new();
+
+        run_recall_repl(
+            &mut input,
+            &mut output,
+            Some(Path::new("history")),
+            |query| {
+                seen.push(query.to_string());
+                Ok(String::new())
+            },
+        )?;
+
+        assert_eq!(seen, vec!["Alice", "Paris"]);
+        let output = String::from_utf8(output)?;
+        assert_eq!(output.matches("warning: history is read-only").count(), 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,272 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use memo_engine::MemoryEngine;
+use serde::Serialize;
+
+use super::{
+    build_engine_config, check_capability, configured_provider_refs, file_config::load_file_config,
+    provider_config::ProvidersConfig,
+};
+
+const WRITE_PROBE_FILE: &str = ".memo-doctor-probe";
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub(crate) struct DoctorCheck {
+    pub name: String,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl DoctorCheck {
+    fn from_result(name: impl Into<String>, result: Result<Option<String>>) -> Self {
+        match result {
+            Ok(detail) => Self {
+                name: name.into(),
+                ok: true,
+                detail,
+            },
+            Err(error) => Self {
+                name: name.into(),
+                ok: false,
+                detail: Some(format!("{error:#}")),
+            },
+        }
+    }
+}
+
+/// Runs local configuration checks in dependency order. Checks that need an earlier step to
+/// pass are skipped when it fails, so one root cause is reported once.
+pub(crate) fn run_doctor(
+    config_dir: &Path,
+    profile: Option<&str>,
+    data_dir: &Path,
+) -> Vec<DoctorCheck> {
+    let mut checks = Vec::new();
+
+    let file_config = match load_file_config(config_dir, profile) {
+        Ok(Some(file_config)) => {
+            checks.push(DoctorCheck::from_result("config.toml", Ok(None)));
+            Some(file_config)
+        }
+        Ok(None) => {
+            checks.push(DoctorCheck::from_result(
+                "config.toml",
+                Err(anyhow::anyhow!(
+                    "not found in {}; run `memo awaken` first",
+                    config_dir.display()
+                )),
+            ));
+            None
+        }
+        Err(error) => {
+            checks.push(DoctorCheck::from_result("config.toml", Err(error)));
+            None
+        }
+    };
+
+    let providers = match load_providers_file(config_dir) {
+        Ok(providers) => {
+            checks.push(DoctorCheck::from_result(
+                "providers.toml",
+                Ok(Some(format!("{} provider(s)", providers.provider_count()))),
+            ));
+            Some(providers)
+        }
+        Err(error) => {
+            checks.push(DoctorCheck::from_result("providers.toml", Err(error)));
+            None
+        }
+    };
+    let providers_ok = providers.is_some();
+
+    if let (Some(file_config), Some(providers)) = (&file_config, &providers) {
+        for (capability, provider_ref) in configured_provider_refs(file_config) {
+            checks.push(DoctorCheck::from_result(
+                format!("{capability} provider `{provider_ref}`"),
+                providers
+                    .provider_config(provider_ref, capability)
+                    .map(|_| None),
+            ));
+        }
+    }
+
+    let data_dir_check =
+        DoctorCheck::from_result("data directory", check_data_dir_writable(data_dir));
+    let data_dir_ok = data_dir_check.ok;
+    checks.push(data_dir_check);
+
+    if data_dir_ok && file_config.is_some() && providers_ok {
+        checks.push(DoctorCheck::from_result(
+            "memory store",
+            build_engine_config(data_dir, config_dir, profile)
+                .and_then(MemoryEngine::open)
+                .and_then(|engine| {
+                    let state = engine.state()?;
+                    let vector_documents = engine.check_vector_index()?;
+                    Ok(Some(format!(
+                        "{} episode(s), {} vector document(s)",
+                        state.episode_count, vector_documents
+                    )))
+                }),
+        ));
+    }
+
+    if let (Some(file_config), Some(providers)) = (&file_config, &providers) {
+        if let Some(provider_ref) = file_config.embed.embedding_provider.as_deref() {
+            let provider_check = check_capability(
+                config_dir,
+                providers,
+                "embedding",
+                provider_ref,
+                &file_config.extract,
+            );
+            checks.push(DoctorCheck {
+                name: "embedding health".to_string(),
+                ok: provider_check.ok,
+                detail: match provider_check.latency_ms {
+                    Some(latency_ms) if provider_check.ok => Some(format!("{latency_ms}ms")),
+                    _ => provider_check.detail,
+                },
+            });
+        }
+    }
+
+    checks
+}
+
+fn load_providers_file(config_dir: &Path) -> Result<ProvidersConfig> {
+    if !config_dir.join("providers.toml").exists() {
+        anyhow::bail!(
+            "not found in {}; run `memo awaken` first",
+            config_dir.display()
+        );
+    }
+    ProvidersConfig::load(config_dir)
+}
+
+fn check_data_dir_writable(data_dir: &Path) -> Result<Option<String>> {
+    if !data_dir.is_dir() {
+        anyhow::bail!(
+            "{} does not exist; run `memo awaken` first",
+            data_dir.display()
+        );
+    }
+    let probe = data_dir.join(WRITE_PROBE_FILE);
+    fs::write(&probe, b"").with_context(|| format!("{} is not writable", data_dir.display()))?;
+    fs::remove_file(&probe)
+        .with_context(|| format!("failed to remove write probe: {}", probe.display()))?;
+    Ok(Some(data_dir.display().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use anyhow::Result;
+    use memo_engine::EngineConfig;
+    use tempfile::TempDir;
+
+    use super::run_doctor;
+
+    #[test]
+    fn doctor_reports_missing_config_and_skips_dependent_checks() -> Result<()> {
+        let temp = TempDir::new()?;
+        let config_dir = temp.path().join(".memo");
+
+        let checks = run_doctor(&config_dir, None, &config_dir.join("data"));
+
+        let names = checks
+            .iter()
+            .map(|check| check.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["config.toml", "providers.toml", "data directory"]
+        );
+        assert!(checks.iter().all(|check| !check.ok));
+        Ok(())
+    }
+
+    #[test]
+    fn doctor_passes_local_checks_without_configured_providers() -> Result<()> {
+        let temp = TempDir::new()?;
+        let config_dir = temp.path().join(".memo");
+        let data_dir = temp.path().join("data");
+        fs::create_dir_all(&config_dir)?;
+        fs::create_dir_all(&data_dir)?;
+        fs::write(
+            config_dir.join("config.toml"),
+            "[engine]\nl3_cache_limit = 8\n",
+        )?;
+        fs::write(
+            config_dir.join("providers.toml"),
+            "[ollama]\n[ollama.embed]\nmodel = \"nomic-embed-text\"\n",
+        )?;
+
+        let checks = run_doctor(&config_dir, None, &data_dir);
+
+        assert!(checks.iter().all(|check| check.ok), "{checks:?}");
+        assert!(checks.iter().any(|check| check.name == "memory store"));
+        assert!(!data_dir.join(super::WRITE_PROBE_FILE).exists());
+        Ok(())
+    }
+
+    #[test]
+    fn doctor_flags_unresolvable_provider_ref() -> Result<()> {
+        let temp = TempDir::new()?;
+        let config_dir = temp.path().join(".memo");
+        let data_dir = temp.path().join("data");
+        fs::create_dir_all(&config_dir)?;
+        fs::create_dir_all(&data_dir)?;
+        fs::write(
+            config_dir.join("config.toml"),
+            "[rerank]\nrerank_provider = \"aliyun.rerank\"\n",
+        )?;
+        fs::write(
+            config_dir.join("providers.toml"),
+            "[openai]\napi_key = \"sk-test\"\n",
+        )?;
+
+        let checks = run_doctor(&config_dir, None, &data_dir);
+
+        let provider_check = checks
+            .iter()
+            .find(|check| check.name == "rerank provider `aliyun.rerank`")
+            .expect("expected provider ref check");
+        assert!(!provider_check.ok);
+        Ok(())
+    }
+
+    #[test]
+    fn doctor_fails_memory_store_check_on_corrupt_vector_index() -> Result<()> {
+        let temp = TempDir::new()?;
+        let config_dir = temp.path().join(".memo");
+        let data_dir = temp.path().join("data");
+        fs::create_dir_all(&config_dir)?;
+        fs::create_dir_all(&data_dir)?;
+        fs::write(
+            config_dir.join("config.toml"),
+            "[engine]\nl3_cache_limit = 8\n",
+        )?;
+        fs::write(
+            config_dir.join("providers.toml"),
+            "[ollama]\n[ollama.embed]\nmodel = \"nomic-embed-text\"\n",
+        )?;
+        fs::write(EngineConfig::new(&data_dir).vector_index_path(), "not json")?;
+
+        let checks = run_doctor(&config_dir, None, &data_dir);
+
+        let store_check = checks
+            .iter()
+            .find(|check| check.name == "memory store")
+            .expect("expected memory store check");
+        assert!(!store_check.ok);
+        assert!(store_check
+            .detail
+            .as_deref()
+            .is_some_and(|detail| detail.contains("failed to decode vector index file")));
+        Ok(())
+    }
+}
@@ -4,6 +4,11 @@ use std::{
 };
 
 use anyhow::{Context, Result};
+use memo_engine::IdScheme;
+
+use super::provider_config::split_provider_ref;
+
+const PROFILES_PREFIX: &str = "profiles.";
 
 #[derive(Debug, Default)]
 pub(crate) struct EmbedConfig {
@@ -17,6 +22,9 @@ pub(crate) struct ExtractConfig {
     pub(crate) extraction_provider: Option<String>,
     pub(crate) min_confidence: Option<f32>,
     pub(crate) normalize_predicates: Option<bool>,
+    pub(crate) system_prompt: Option<String>,
+    pub(crate) prompt_file: Option<String>,
+    pub(crate) temperature: Option<f32>,
     pub(crate) max_retries: Option<usize>,
     pub(crate) retry_backoff_ms: Option<u64>,
 }
@@ -36,6 +44,10 @@ pub(crate) struct StorageConfig {
 #[derive(Debug, Default)]
 pub(crate) struct EngineConfig {
     pub(crate) l3_cache_limit: Option<usize>,
+    pub(crate) recency_weight: Option<f32>,
+    pub(crate) recency_half_life_days: Option<u32>,
+    pub(crate) id_scheme: Option<IdScheme>,
+    pub(crate) max_recall_limit: Option<usize>,
 }
 
 #[derive(Debug, Default)]
@@ -47,15 +59,24 @@ pub(crate) struct FileConfig {
     pub(crate) rerank: RerankConfig,
 }
 
-pub(crate) fn load_file_config(config_dir: &Path) -> Result<Option<FileConfig>> {
+pub(crate) fn load_file_config(
+    config_dir: &Path,
+    profile: Option<&str>,
+) -> Result<Option<FileConfig>> {
     let config_path = config_dir.join("config.toml");
     if !config_path.exists() {
+        if let Some(profile) = profile {
+            anyhow::bail!(
+                "profile `{profile}` selected but {} does not exist",
+                config_path.display()
+            );
+        }
         return Ok(None);
     }
 
     let config_text = fs::read_to_string(&config_path)
         .with_context(|| format!("failed to read config file: {}", config_path.display()))?;
-    let file_config = parse_app_config(&config_text)
+    let file_config = parse_app_config_with_profile(&config_text, profile)
         .with_context(|| format!("failed to parse config file: {}", config_path.display()))?;
     Ok(Some(file_config))
 }
@@ -69,7 +90,20 @@ pub(crate) fn resolve_relative_to_dir(base_dir: &Path, path: &Path) -> PathBuf {
 }
 
 pub(crate) fn parse_app_config(contents: &str) -> Result<FileConfig> {
+    parse_app_config_with_profile(contents, None)
+}
+
+/// Parses `config.toml`, then applies the `[profiles.<profile>.<section>]` tables of the
+/// selected profile on top of the base sections. Tables of other profiles are still checked
+/// so `memo config validate` reports their mistakes.
+pub(crate) fn parse_app_config_with_profile(
+    contents: &str,
+    profile: Option<&str>,
+) -> Result<FileConfig> {
     let mut config = FileConfig::default();
+    let mut unused_profiles = FileConfig::default();
+    let mut profile_entries = Vec::new();
+    let mut profile_found = false;
     let mut section: Option<String> = None;
 
     for (line_no, raw_line) in contents.lines().enumerate() {
@@ -79,70 +113,163 @@ pub(crate) fn parse_app_config(contents: &str) -> Result<FileConfig> {
         }
 
         if let Some(section_name) = parse_section_header(line) {
+            if let Some(name) = section_name.strip_prefix(PROFILES_PREFIX) {
+                profile_found |= Some(name.split('.').next().unwrap_or(name)) == profile;
+            }
             section = Some(section_name.to_string());
             continue;
         }
 
         let (key, value) = parse_key_value(line)
             .with_context(|| format!("invalid config line {}", line_no + 1))?;
-        match section.as_deref() {
-            Some("storage") => {
-                if key == "data_dir" {
-                    config.storage.data_dir = Some(parse_string(value)?.to_string());
-                }
+        match section.as_deref().and_then(split_profile_section) {
+            Some((name, inner)) if Some(name) == profile => {
+                profile_entries.push((inner.to_string(), key, value));
             }
-            Some("engine") => {
-                if key == "l3_cache_limit" {
-                    config.engine.l3_cache_limit = Some(value.parse::<usize>()?);
-                }
+            Some((_, inner)) => apply_config_entry(&mut unused_profiles, Some(inner), key, value)?,
+            None => apply_config_entry(&mut config, section.as_deref(), key, value)?,
+        }
+    }
+
+    if let Some(profile) = profile {
+        if !profile_found {
+            anyhow::bail!(
+                "profile `{profile}` is not defined; add a [profiles.{profile}.<section>] table"
+            );
+        }
+    }
+    for (section, key, value) in profile_entries {
+        apply_config_entry(&mut config, Some(&section), key, value).with_context(|| {
+            format!("invalid value in profile `{}`", profile.unwrap_or_default())
+        })?;
+    }
+
+    Ok(config)
+}
+
+fn split_profile_section(section: &str) -> Option<(&str, &str)> {
+    section.strip_prefix(PROFILES_PREFIX)?.split_once('.')
+}
+
+/// Applies one `key = value` line; a rejected value is reported as `<section>.<key>` so the
+/// message points at the setting to fix.
+fn apply_config_entry(
+    config: &mut FileConfig,
+    section: Option<&str>,
+    key: &str,
+    value: &str,
+) -> Result<()> {
+    apply_config_value(config, section, key, value).with_context(|| match section {
+        Some(section) => format!("invalid value for `{section}.{key}`"),
+        None => format!("invalid value for `{key}`"),
+    })
+}
+
+fn apply_config_value(
+    config: &mut FileConfig,
+    section: Option<&str>,
+    key: &str,
+    value: &str,
+) -> Result<()> {
+    match section {
+        Some("storage") => {
+            if key == "data_dir" {
+                config.storage.data_dir = Some(parse_string(value)?.to_string());
             }
-            Some("embed") => match key {
-                "embedding_provider" => {
-                    config.embed.embedding_provider = Some(parse_string(value)?.to_string());
-                }
-                "max_retries" => {
-                    config.embed.max_retries = Some(value.parse::<usize>()?);
-                }
-                "retry_backoff_ms" => {
-                    config.embed.retry_backoff_ms = Some(value.parse::<u64>()?);
-                }
-                _ => {}
-            },
-            Some("extract") => match key {
-                "extraction_provider" => {
-                    config.extract.extraction_provider = Some(parse_string(value)?.to_string());
-                }
-                "min_confidence" => {
-                    config.extract.min_confidence = Some(value.parse::<f32>()?);
-                }
-                "normalize_predicates" => {
-                    config.extract.normalize_predicates = Some(parse_bool(value)?);
-                }
-                "max_retries" => {
-                    config.extract.max_retries = Some(value.parse::<usize>()?);
+        }
+        Some("engine") => match key {
+            "l3_cache_limit" => {
+                config.engine.l3_cache_limit = Some(value.parse::<usize>()?);
+            }
+            "recency_weight" => {
+                let weight = value.parse::<f32>()?;
+                if !(0.0..=1.0).contains(&weight) {
+                    anyhow::bail!("recency_weight must be between 0.0 and 1.0, got {weight}");
                 }
-                "retry_backoff_ms" => {
-                    config.extract.retry_backoff_ms = Some(value.parse::<u64>()?);
+                config.engine.recency_weight = Some(weight);
+            }
+            "recency_half_life_days" => {
+                let days = value.parse::<u32>()?;
+                if days == 0 {
+                    anyhow::bail!("recency_half_life_days must be greater than 0");
                 }
-                _ => {}
-            },
-            Some("rerank") => match key {
-                "rerank_provider" => {
-                    config.rerank.rerank_provider = Some(parse_string(value)?.to_string());
+                config.engine.recency_half_life_days = Some(days);
+            }
+            "max_recall_limit" => {
+                let limit = value.parse::<usize>()?;
+                if limit == 0 {
+                    anyhow::bail!("max_recall_limit must be greater than 0");
                 }
-                "max_retries" => {
-                    config.rerank.max_retries = Some(value.parse::<usize>()?);
+                config.engine.max_recall_limit = Some(limit);
+            }
+            "id_scheme" => {
+                config.engine.id_scheme = Some(parse_string(value)?.parse::<IdScheme>()?);
+            }
+            _ => {}
+        },
+        Some("embed") => match key {
+            "embedding_provider" => {
+                config.embed.embedding_provider = parse_provider_ref(value)?;
+            }
+            "max_retries" => {
+                config.embed.max_retries = Some(value.parse::<usize>()?);
+            }
+            "retry_backoff_ms" => {
+                config.embed.retry_backoff_ms = Some(value.parse::<u64>()?);
+            }
+            _ => {}
+        },
+        Some("extract") => match key {
+            "extraction_provider" => {
+                config.extract.extraction_provider = parse_provider_ref(value)?;
+            }
+            "min_confidence" => {
+                let confidence = value.parse::<f32>()?;
+                if !(0.0..=1.0).contains(&confidence) {
+                    anyhow::bail!("min_confidence must be between 0.0 and 1.0, got {confidence}");
                 }
-                "retry_backoff_ms" => {
-                    config.rerank.retry_backoff_ms = Some(value.parse::<u64>()?);
+                config.extract.min_confidence = Some(confidence);
+            }
+            "normalize_predicates" => {
+                config.extract.normalize_predicates = Some(parse_bool(value)?);
+            }
+            "system_prompt" => {
+                config.extract.system_prompt = Some(parse_string(value)?.to_string());
+            }
+            "prompt_file" => {
+                config.extract.prompt_file = Some(parse_string(value)?.to_string());
+            }
+            "temperature" => {
+                let temperature = value.parse::<f32>()?;
+                if !(0.0..=2.0).contains(&temperature) {
+                    anyhow::bail!("temperature must be between 0.0 and 2.0, got {temperature}");
                 }
-                _ => {}
-            },
+                config.extract.temperature = Some(temperature);
+            }
+            "max_retries" => {
+                config.extract.max_retries = Some(value.parse::<usize>()?);
+            }
+            "retry_backoff_ms" => {
+                config.extract.retry_backoff_ms = Some(value.parse::<u64>()?);
+            }
             _ => {}
-        }
+        },
+        Some("rerank") => match key {
+            "rerank_provider" => {
+                config.rerank.rerank_provider = parse_provider_ref(value)?;
+            }
+            "max_retries" => {
+                config.rerank.max_retries = Some(value.parse::<usize>()?);
+            }
+            "retry_backoff_ms" => {
+                config.rerank.retry_backoff_ms = Some(value.parse::<u64>()?);
+            }
+            _ => {}
+        },
+        _ => {}
     }
 
-    Ok(config)
+    Ok(())
 }
 
 fn strip_comments(line: &str) -> &str {
@@ -167,6 +294,16 @@ fn parse_string(value: &str) -> Result<&str> {
         .with_context(|| format!("expected quoted string, got `{value}`"))
 }
 
+/// An empty provider reference (`""`) clears the provider, so a profile can turn it off.
+fn parse_provider_ref(value: &str) -> Result<Option<String>> {
+    let provider_ref = parse_string(value)?;
+    if provider_ref.is_empty() {
+        return Ok(None);
+    }
+    split_provider_ref(provider_ref)?;
+    Ok(Some(provider_ref.to_string()))
+}
+
 fn parse_bool(value: &str) -> Result<bool> {
     match value.trim() {
         "true" => Ok(true),
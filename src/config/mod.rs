@@ -1,19 +1,28 @@
 mod app_home;
+mod doctor;
 mod file_config;
 mod provider_config;
+mod saved_recalls;
 mod templates;
 
 use std::{
+    collections::HashMap,
+    fs,
     path::{Path, PathBuf},
     sync::Arc,
 };
 
 use anyhow::{Context, Result};
+use lmkit::ProviderConfig;
 use memo_engine::EngineConfig;
 
-use crate::providers::adapters::embedding::LmkitEmbeddingAdapter;
-use crate::providers::adapters::extraction::{ExtractionCleanupOptions, LmkitExtractionAdapter};
+use crate::providers::adapters::embedding::{EmbeddingOptions, LmkitEmbeddingAdapter};
+use crate::providers::adapters::extraction::{
+    render_template, ExtractionCleanupOptions, ExtractionRequestOptions, LmkitExtractionAdapter,
+    DEFAULT_EXTRACTION_TEMPERATURE, EXTRACTION_SYSTEM_PROMPT,
+};
 use crate::providers::adapters::rerank::LmkitRerankAdapter;
+use crate::providers::health::{self, ProviderCheck};
 use crate::providers::runtime::{
     ProviderRetryPolicy, RetryingEmbeddingProvider, RetryingExtractionProvider,
     RetryingRerankProvider,
@@ -25,37 +34,62 @@ use crate::providers::status::{
 };
 
 pub(crate) use app_home::{initialize_app_home, InitReport};
-use file_config::{load_file_config, resolve_relative_to_dir, ExtractConfig};
-use provider_config::{load_provider_config, provider_ref_uses_placeholder_key};
+pub(crate) use doctor::{run_doctor, DoctorCheck};
+use file_config::{load_file_config, resolve_relative_to_dir, ExtractConfig, FileConfig};
+use provider_config::ProvidersConfig;
+pub(crate) use saved_recalls::{find_saved_recall, load_saved_recalls, save_recall, SavedRecall};
 
 pub(crate) fn build_engine_config(
     data_dir: impl Into<PathBuf>,
     config_dir: &Path,
+    profile: Option<&str>,
 ) -> Result<EngineConfig> {
     let data_dir = data_dir.into();
     let mut engine_config = EngineConfig::new(&data_dir);
     let provider_runtime = ProviderRuntimeRecorder::new(&data_dir);
-    let Some(file_config) = load_file_config(config_dir)? else {
+    let Some(file_config) = load_file_config(config_dir, profile)? else {
         return Ok(engine_config);
     };
 
     if let Some(limit) = file_config.engine.l3_cache_limit {
         engine_config.l3_cache_limit = limit;
     }
+    if let Some(weight) = file_config.engine.recency_weight {
+        engine_config.recency_weight = weight;
+    }
+    if let Some(days) = file_config.engine.recency_half_life_days {
+        engine_config.recency_half_life_days = days;
+    }
+    if let Some(limit) = file_config.engine.max_recall_limit {
+        engine_config.max_recall_limit = limit;
+    }
+    if let Some(id_scheme) = file_config.engine.id_scheme {
+        engine_config.id_scheme = id_scheme;
+    }
+    if configured_provider_refs(&file_config).is_empty() {
+        return Ok(engine_config);
+    }
+    let providers = ProvidersConfig::load(config_dir)?;
 
     if let Some(provider_ref) = file_config.embed.embedding_provider.as_deref() {
-        if provider_ref_uses_placeholder_key(config_dir, provider_ref)
+        if providers
+            .uses_placeholder_key(provider_ref)
             .with_context(|| format!("failed to resolve embedding provider `{provider_ref}`"))?
         {
         } else {
-            let provider_config = load_provider_config(config_dir, provider_ref, "embedding")?;
+            let provider_config = providers.provider_config(provider_ref, "embedding")?;
             let adapter = RetryingEmbeddingProvider::new(
-                LmkitEmbeddingAdapter::new(provider_config)?,
+                LmkitEmbeddingAdapter::new_with_options(
+                    provider_config,
+                    embedding_options(&providers, provider_ref)?,
+                )?,
                 provider_ref,
-                ProviderRetryPolicy::new(
+                retry_policy(
+                    &providers,
+                    provider_ref,
                     file_config.embed.max_retries,
                     file_config.embed.retry_backoff_ms,
-                ),
+                )?,
                 provider_runtime.clone(),
             );
             engine_config = engine_config.with_embedding_provider(Arc::new(adapter));
@@ -63,21 +97,21 @@ pub(crate) fn build_engine_config(
     }
 
     if let Some(provider_ref) = file_config.extract.extraction_provider.as_deref() {
-        if provider_ref_uses_placeholder_key(config_dir, provider_ref)
+        if providers
+            .uses_placeholder_key(provider_ref)
             .with_context(|| format!("failed to resolve extraction provider `{provider_ref}`"))?
         {
         } else {
-            let provider_config = load_provider_config(config_dir, provider_ref, "extraction")?;
+            let provider_config = providers.provider_config(provider_ref, "extraction")?;
             let adapter = RetryingExtractionProvider::new(
-                LmkitExtractionAdapter::new_with_options(
-                    provider_config,
-                    extraction_cleanup_options(&file_config.extract),
-                )?,
+                extraction_adapter(config_dir, provider_config, &file_config.extract)?,
                 provider_ref,
-                ProviderRetryPolicy::new(
+                retry_policy(
+                    &providers,
+                    provider_ref,
                     file_config.extract.max_retries,
                     file_config.extract.retry_backoff_ms,
-                ),
+                )?,
                 provider_runtime.clone(),
             );
             engine_config = engine_config.with_extraction_provider(Arc::new(adapter));
@@ -85,18 +119,21 @@ pub(crate) fn build_engine_config(
     }
 
     if let Some(provider_ref) = file_config.rerank.rerank_provider.as_deref() {
-        if provider_ref_uses_placeholder_key(config_dir, provider_ref)
+        if providers
+            .uses_placeholder_key(provider_ref)
             .with_context(|| format!("failed to resolve rerank provider `{provider_ref}`"))?
         {
         } else {
-            let provider_config = load_provider_config(config_dir, provider_ref, "rerank")?;
+            let provider_config = providers.provider_config(provider_ref, "rerank")?;
             let adapter = RetryingRerankProvider::new(
-                LmkitRerankAdapter::new(provider_config)?,
+                LmkitRerankAdapter::new(provider_config, provider_ref)?,
                 provider_ref,
-                ProviderRetryPolicy::new(
+                retry_policy(
+                    &providers,
+                    provider_ref,
                     file_config.rerank.max_retries,
                     file_config.rerank.retry_backoff_ms,
-                ),
+                )?,
                 provider_runtime,
             );
             engine_config = engine_config.with_rerank_provider(Arc::new(adapter));
@@ -106,8 +143,35 @@ pub(crate) fn build_engine_config(
     Ok(engine_config)
 }
 
-pub(crate) fn resolve_configured_data_dir(config_dir: &Path) -> Result<Option<PathBuf>> {
-    let Some(file_config) = load_file_config(config_dir)? else {
+fn embedding_options(providers: &ProvidersConfig, provider_ref: &str) -> Result<EmbeddingOptions> {
+    let service = providers.service(provider_ref)?;
+    Ok(EmbeddingOptions {
+        document_prefix: service.document_prefix.clone(),
+        max_tokens: service.max_tokens,
+        truncate: service.truncate.unwrap_or(false),
+    })
+}
+
+/// Capability-level retry settings in `config.toml` win over the service defaults in
+/// `providers.toml`.
+fn retry_policy(
+    providers: &ProvidersConfig,
+    provider_ref: &str,
+    max_retries: Option<usize>,
+    retry_backoff_ms: Option<u64>,
+) -> Result<ProviderRetryPolicy> {
+    let service = providers.service(provider_ref)?;
+    Ok(ProviderRetryPolicy::new(
+        max_retries.or(service.max_retries),
+        retry_backoff_ms.or(service.retry_backoff_ms),
+    ))
+}
+
+pub(crate) fn resolve_configured_data_dir(
+    config_dir: &Path,
+    profile: Option<&str>,
+) -> Result<Option<PathBuf>> {
+    let Some(file_config) = load_file_config(config_dir, profile)? else {
         return Ok(None);
     };
     Ok(file_config
@@ -119,9 +183,10 @@ pub(crate) fn resolve_configured_data_dir(config_dir: &Path) -> Result<Option<Pa
 
 pub(crate) fn load_provider_readiness(
     config_dir: &Path,
+    profile: Option<&str>,
     runtime: &ProviderRuntimeSummary,
 ) -> ProviderReadinessSummary {
-    let file_config = match load_file_config(config_dir) {
+    let file_config = match load_file_config(config_dir, profile) {
         Ok(value) => value,
         Err(error) => {
             return ProviderReadinessSummary {
@@ -129,7 +194,7 @@ pub(crate) fn load_provider_readiness(
                     capability: "config".to_string(),
                     provider_ref: None,
                     status: ProviderReadiness::Degraded,
-                    detail: Some(error.to_string()),
+                    detail: Some(format!("{error:#}")),
                 }],
             };
         }
@@ -148,20 +213,21 @@ pub(crate) fn load_provider_readiness(
         return ProviderReadinessSummary { capabilities };
     };
 
+    let providers = ProvidersConfig::load(config_dir);
     capabilities.push(provider_readiness_for_ref(
-        config_dir,
+        &providers,
         runtime,
         "embedding",
         file_config.embed.embedding_provider.as_deref(),
     ));
     capabilities.push(provider_readiness_for_ref(
-        config_dir,
+        &providers,
         runtime,
         "extraction",
         file_config.extract.extraction_provider.as_deref(),
     ));
     capabilities.push(provider_readiness_for_ref(
-        config_dir,
+        &providers,
         runtime,
         "rerank",
         file_config.rerank.rerank_provider.as_deref(),
@@ -170,8 +236,10 @@ pub(crate) fn load_provider_readiness(
     ProviderReadinessSummary { capabilities }
 }
 
+/// `providers` is the outcome of loading providers.toml; a load failure only degrades the
+/// capabilities that are actually configured.
 fn provider_readiness_for_ref(
-    config_dir: &Path,
+    providers: &Result<ProvidersConfig>,
     runtime: &ProviderRuntimeSummary,
     capability: &str,
     provider_ref: Option<&str>,
@@ -185,7 +253,11 @@ fn provider_readiness_for_ref(
         };
     };
 
-    match provider_ref_uses_placeholder_key(config_dir, provider_ref) {
+    let uses_placeholder_key = match providers {
+        Ok(providers) => providers.uses_placeholder_key(provider_ref),
+        Err(error) => Err(anyhow::anyhow!("{error:#}")),
+    };
+    match uses_placeholder_key {
         Ok(true) => {
             return ProviderCapabilityReadiness {
                 capability: capability.to_string(),
@@ -229,6 +301,161 @@ fn provider_readiness_for_ref(
     }
 }
 
+pub(crate) fn validate_providers(
+    config_dir: &Path,
+    profile: Option<&str>,
+) -> Result<Vec<ProviderCheck>> {
+    let file_config = load_file_config(config_dir, profile)?.with_context(|| {
+        format!(
+            "config.toml not found in {}; run `memo awaken` first",
+            config_dir.display()
+        )
+    })?;
+
+    let provider_refs = configured_provider_refs(&file_config);
+    if provider_refs.is_empty() {
+        return Ok(Vec::new());
+    }
+    let providers = ProvidersConfig::load(config_dir)?;
+    Ok(provider_refs
+        .into_iter()
+        .map(|(capability, provider_ref)| {
+            check_capability(
+                config_dir,
+                &providers,
+                capability,
+                provider_ref,
+                &file_config.extract,
+            )
+        })
+        .collect())
+}
+
+fn configured_provider_refs(file_config: &FileConfig) -> Vec<(&'static str, &str)> {
+    [
+        ("embedding", file_config.embed.embedding_provider.as_deref()),
+        (
+            "extraction",
+            file_config.extract.extraction_provider.as_deref(),
+        ),
+        ("rerank", file_config.rerank.rerank_provider.as_deref()),
+    ]
+    .into_iter()
+    .filter_map(|(capability, provider_ref)| provider_ref.map(|value| (capability, value)))
+    .collect()
+}
+
+pub(crate) fn test_provider(
+    config_dir: &Path,
+    profile: Option<&str>,
+    provider_name: &str,
+) -> Result<Vec<ProviderCheck>> {
+    let providers = ProvidersConfig::load(config_dir)?;
+    let service_names = providers.service_names(provider_name)?;
+    if service_names.is_empty() {
+        anyhow::bail!("provider `{provider_name}` has no services in providers.toml");
+    }
+
+    let extract_config = load_file_config(config_dir, profile)?
+        .map(|file_config| file_config.extract)
+        .unwrap_or_default();
+    Ok(service_names
+        .iter()
+        .map(|service_name| {
+            let provider_ref = format!("{provider_name}.{service_name}");
+            match service_capability(service_name) {
+                Some(capability) => check_capability(
+                    config_dir,
+                    &providers,
+                    capability,
+                    &provider_ref,
+                    &extract_config,
+                ),
+                None => ProviderCheck::from_result(
+                    service_name,
+                    &provider_ref,
+                    Err(anyhow::anyhow!(
+                        "unknown service kind; expected embed, extract, or rerank"
+                    )),
+                ),
+            }
+        })
+        .collect())
+}
+
+fn service_capability(service_name: &str) -> Option<&'static str> {
+    match service_name {
+        "embed" | "embedding" => Some("embedding"),
+        "extract" | "extraction" | "chat" | "llm" => Some("extraction"),
+        "rerank" => Some("rerank"),
+        _ => None,
+    }
+}
+
+fn check_capability(
+    config_dir: &Path,
+    providers: &ProvidersConfig,
+    capability: &str,
+    provider_ref: &str,
+    extract_config: &ExtractConfig,
+) -> ProviderCheck {
+    let result = match providers.uses_placeholder_key(provider_ref) {
+        Ok(true) => Err(anyhow::anyhow!(
+            "provider api_key is still a template placeholder"
+        )),
+        Ok(false) => providers
+            .provider_config(provider_ref, capability)
+            .and_then(|provider_config| match capability {
+                "embedding" => health::check_embedding(&LmkitEmbeddingAdapter::new_with_options(
+                    provider_config,
+                    embedding_options(providers, provider_ref)?,
+                )?),
+                "extraction" => health::check_extraction(&extraction_adapter(
+                    config_dir,
+                    provider_config,
+                    extract_config,
+                )?),
+                "rerank" => {
+                    health::check_rerank(&LmkitRerankAdapter::new(provider_config, provider_ref)?)
+                }
+                other => anyhow::bail!("unsupported provider capability `{other}`"),
+            }),
+        Err(error) => Err(error),
+    };
+    ProviderCheck::from_result(capability, provider_ref, result)
+}
+
+fn extraction_adapter(
+    config_dir: &Path,
+    provider_config: ProviderConfig,
+    config: &ExtractConfig,
+) -> Result<LmkitExtractionAdapter> {
+    LmkitExtractionAdapter::new_with_options(
+        provider_config,
+        extraction_cleanup_options(config),
+        ExtractionRequestOptions {
+            system_prompt: config.system_prompt.clone(),
+            extraction_prompt: load_extraction_prompt(config_dir, config)?,
+            temperature: config.temperature.unwrap_or(DEFAULT_EXTRACTION_TEMPERATURE),
+        },
+    )
+}
+
+/// Reads `[extract] prompt_file`, relative to the config dir, with `{{default}}` expanding to
+/// the built-in prompt so a custom file can extend it instead of replacing it.
+fn load_extraction_prompt(config_dir: &Path, config: &ExtractConfig) -> Result<Option<String>> {
+    let Some(prompt_file) = config.prompt_file.as_deref() else {
+        return Ok(None);
+    };
+    let path = resolve_relative_to_dir(config_dir, Path::new(prompt_file));
+    let template = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read extraction prompt file: {}", path.display()))?;
+    Ok(Some(render_template(
+        &template,
+        &HashMap::from([("default", EXTRACTION_SYSTEM_PROMPT)]),
+    )))
+}
+
 fn extraction_cleanup_options(config: &ExtractConfig) -> ExtractionCleanupOptions {
     ExtractionCleanupOptions {
         min_confidence: config.min_confidence.unwrap_or(0.5),
@@ -243,14 +470,17 @@ mod tests {
     use anyhow::Result;
     use tempfile::TempDir;
 
-    use crate::providers::status::{ProviderReadiness, ProviderRuntimeSummary};
+    use crate::providers::{
+        adapters::extraction::EXTRACTION_SYSTEM_PROMPT,
+        status::{ProviderReadiness, ProviderRuntimeSummary},
+    };
 
     use super::{
         build_engine_config,
-        file_config::parse_app_config,
-        initialize_app_home, load_provider_readiness,
-        provider_config::{parse_providers_config, provider_ref_uses_placeholder_key_from_text},
-        resolve_configured_data_dir,
+        file_config::{parse_app_config, parse_app_config_with_profile},
+        initialize_app_home, load_extraction_prompt, load_provider_readiness,
+        provider_config::{interpolate_env, parse_providers_config, ProvidersConfig},
+        resolve_configured_data_dir, retry_policy, test_provider, validate_providers,
     };
 
     #[test]
@@ -296,7 +526,7 @@ mod tests {
             "[openai]\napi_key = \"sk-test\"\n[openai.embed]\nbase_url = \"https://api.openai.com/v1\"\nmodel = \"text-embedding-3-small\"\ndimension = 1536\n[openai.extract]\nbase_url = \"https://api.openai.com/v1\"\nmodel = \"gpt-4o-mini\"\n",
         )?;
 
-        let config = build_engine_config(&data_dir, &config_dir)?;
+        let config = build_engine_config(&data_dir, &config_dir, None)?;
 
         assert_eq!(config.vector_dimension, 1536);
         let provider = config
@@ -323,11 +553,12 @@ mod tests {
             "[openai]\napi_key = \"sk-your-openai-api-key\"\n[openai.embed]\nbase_url = \"https://api.openai.com/v1\"\nmodel = \"text-embedding-3-small\"\ndimension = 1536\n[openai.extract]\nbase_url = \"https://api.openai.com/v1\"\nmodel = \"gpt-4o-mini\"\n",
         )?;
 
-        let config = build_engine_config(&data_dir, &config_dir)?;
+        let config = build_engine_config(&data_dir, &config_dir, None)?;
         assert!(config.embedding_provider.is_none());
         assert!(config.extraction_provider.is_none());
 
-        let readiness = load_provider_readiness(&config_dir, &ProviderRuntimeSummary::default());
+        let readiness =
+            load_provider_readiness(&config_dir, None, &ProviderRuntimeSummary::default());
         assert!(readiness.capabilities.iter().any(|capability| {
             capability.capability == "embedding"
                 && capability.status == ProviderReadiness::PlaceholderKey
@@ -343,10 +574,48 @@ mod tests {
     fn provider_placeholder_detection_allows_local_ollama_empty_key() -> Result<()> {
         let providers = "[ollama]\napi_key = \"\"\n[ollama.embed]\nbase_url = \"http://127.0.0.1:11434/v1\"\nmodel = \"bge-m3\"\ndimension = 1024\n";
 
-        assert!(!provider_ref_uses_placeholder_key_from_text(
-            providers,
-            "ollama.embed"
-        )?);
+        assert!(!parse_providers_config(providers)?.uses_placeholder_key("ollama.embed")?);
+        Ok(())
+    }
+
+    #[test]
+    fn provider_placeholder_detection_allows_empty_key_for_any_local_server() -> Result<()> {
+        let providers = "[vllm]\napi_key = \"\"\n[vllm.extract]\nbase_url = \"http://localhost:8000/v1\"\nmodel = \"qwen\"\n\
+             [remote]\napi_key = \"\"\n[remote.extract]\nbase_url = \"https://localhost.example.com/v1\"\nmodel = \"qwen\"\n";
+
+        assert!(!parse_providers_config(providers)?.uses_placeholder_key("vllm.extract")?);
+        for base_url in [
+            "http://[::1]:11434/v1",
+            "http://127.0.0.2:8000",
+            "http://LOCALHOST/v1",
+        ] {
+            let providers = format!(
+                "[local]\napi_key = \"\"\n[local.extract]\nbase_url = \"{base_url}\"\nmodel = \"qwen\"\n"
+            );
+            assert!(
+                !parse_providers_config(&providers)?.uses_placeholder_key("local.extract")?,
+                "{base_url} should count as local"
+            );
+        }
+        assert!(parse_providers_config(providers)?.uses_placeholder_key("remote.extract")?);
+        Ok(())
+    }
+
+    #[test]
+    fn provider_config_reports_every_missing_service_field() -> Result<()> {
+        let temp = TempDir::new()?;
+        fs::write(
+            temp.path().join("providers.toml"),
+            "[openai]\napi_key = \"sk-test\"\n[openai.extract]\ntimeout_ms = 1000\n",
+        )?;
+
+        let error = ProvidersConfig::load(temp.path())?
+            .provider_config("openai.extract", "extraction")
+            .expect_err("expected missing service fields");
+
+        assert!(error
+            .chain()
+            .any(|cause| cause.to_string() == "missing `base_url`, `model`"));
         Ok(())
     }
 
@@ -365,7 +634,7 @@ mod tests {
             "[aliyun]\napi_key = \"sk-test\"\n[aliyun.rerank]\nbase_url = \"https://dashscope.aliyuncs.com/api/v1\"\nmodel = \"gte-rerank\"\n",
         )?;
 
-        let config = build_engine_config(&data_dir, &config_dir)?;
+        let config = build_engine_config(&data_dir, &config_dir, None)?;
 
         assert!(config.rerank_provider.is_some());
         Ok(())
@@ -386,13 +655,14 @@ mod tests {
             "[openai]\napi_key = \"sk-test\"\n",
         )?;
 
-        let error = match build_engine_config(&data_dir, &config_dir) {
+        let error = match build_engine_config(&data_dir, &config_dir, None) {
             Ok(_) => panic!("expected invalid provider ref"),
             Err(error) => error,
         };
-        assert!(error
+        assert!(error.to_string().contains("failed to parse config file"));
+        assert!(error.chain().any(|cause| cause
             .to_string()
-            .contains("failed to resolve embedding provider `openai`"));
+            .contains("invalid value for `embed.embedding_provider`")));
         assert!(error.chain().any(|cause| cause
             .to_string()
             .contains("must look like `<provider>.<service>`")));
@@ -429,12 +699,197 @@ mod tests {
             "[engine]\nl3_cache_limit = 7\n",
         )?;
 
-        let config = build_engine_config(&data_dir, &config_dir)?;
+        let config = build_engine_config(&data_dir, &config_dir, None)?;
 
         assert_eq!(config.l3_cache_limit, 7);
         Ok(())
     }
 
+    #[test]
+    fn build_engine_config_reads_recency_weighting_from_app_config() -> Result<()> {
+        let temp = TempDir::new()?;
+        let config_dir = temp.path().join(".memo");
+        let data_dir = temp.path().join("memory-data");
+        fs::create_dir_all(&config_dir)?;
+        fs::write(
+            config_dir.join("config.toml"),
+            "[engine]\nrecency_weight = 0.25\nrecency_half_life_days = 90\n",
+        )?;
+
+        let config = build_engine_config(&data_dir, &config_dir, None)?;
+
+        assert_eq!(config.recency_weight, 0.25);
+        assert_eq!(config.recency_half_life_days, 90);
+        Ok(())
+    }
+
+    #[test]
+    fn build_engine_config_reads_id_scheme_from_app_config() -> Result<()> {
+        let temp = TempDir::new()?;
+        let config_dir = temp.path().join(".memo");
+        let data_dir = temp.path().join("memory-data");
+        fs::create_dir_all(&config_dir)?;
+        fs::write(
+            config_dir.join("config.toml"),
+            "[engine]\nid_scheme = \"v7\"\n",
+        )?;
+
+        let config = build_engine_config(&data_dir, &config_dir, None)?;
+
+        assert_eq!(config.id_scheme, memo_engine::IdScheme::V7);
+        Ok(())
+    }
+
+    #[test]
+    fn build_engine_config_reads_max_recall_limit_from_app_config() -> Result<()> {
+        let temp = TempDir::new()?;
+        let config_dir = temp.path().join(".memo");
+        let data_dir = temp.path().join("memory-data");
+        fs::create_dir_all(&config_dir)?;
+        fs::write(
+            config_dir.join("config.toml"),
+            "[engine]\nmax_recall_limit = 50\n",
+        )?;
+
+        let config = build_engine_config(&data_dir, &config_dir, None)?;
+
+        assert_eq!(config.max_recall_limit, 50);
+        assert!(parse_app_config("[engine]\nmax_recall_limit = 0\n").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn build_engine_config_applies_the_profile_it_is_given() -> Result<()> {
+        let temp = TempDir::new()?;
+        let config_dir = temp.path().join(".memo");
+        let data_dir = temp.path().join("memory-data");
+        fs::create_dir_all(&config_dir)?;
+        fs::write(
+            config_dir.join("config.toml"),
+            "[engine]\nmax_recall_limit = 50\n[profiles.fast.engine]\nmax_recall_limit = 20\n",
+        )?;
+
+        let base = build_engine_config(&data_dir, &config_dir, None)?;
+        let fast = build_engine_config(&data_dir, &config_dir, Some("fast"))?;
+
+        assert_eq!(base.max_recall_limit, 50);
+        assert_eq!(fast.max_recall_limit, 20);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_app_config_applies_selected_profile_over_base_sections() -> Result<()> {
+        let contents = "[profiles.fast.engine]\nmax_recall_limit = 20\n\
+             [engine]\nmax_recall_limit = 500\nl3_cache_limit = 64\n\
+             [embed]\nembedding_provider = \"openai.embed\"\n\
+             [profiles.offline.embed]\nembedding_provider = \"\"\n";
+
+        let base = parse_app_config(contents)?;
+        let fast = parse_app_config_with_profile(contents, Some("fast"))?;
+        let offline = parse_app_config_with_profile(contents, Some("offline"))?;
+
+        assert_eq!(base.engine.max_recall_limit, Some(500));
+        assert_eq!(fast.engine.max_recall_limit, Some(20));
+        assert_eq!(fast.engine.l3_cache_limit, Some(64));
+        assert_eq!(
+            fast.embed.embedding_provider.as_deref(),
+            Some("openai.embed")
+        );
+        assert_eq!(offline.embed.embedding_provider, None);
+        assert_eq!(offline.engine.max_recall_limit, Some(500));
+
+        let missing = parse_app_config_with_profile(contents, Some("thorough"))
+            .expect_err("expected unknown profile to fail");
+        assert!(missing
+            .to_string()
+            .contains("profile `thorough` is not defined"));
+        assert!(parse_app_config("[profiles.fast.engine]\nmax_recall_limit = 0\n").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_app_config_reads_extraction_system_prompt() -> Result<()> {
+        let config = parse_app_config(
+            "[extract]\nsystem_prompt = \"You index a team engineering wiki.\"\n",
+        )?;
+
+        assert_eq!(
+            config.extract.system_prompt.as_deref(),
+            Some("You index a team engineering wiki.")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_app_config_reads_and_bounds_extraction_temperature() -> Result<()> {
+        let config = parse_app_config("[extract]\ntemperature = 0.3\n")?;
+
+        assert_eq!(config.extract.temperature, Some(0.3));
+        assert!(parse_app_config("[extract]\ntemperature = 2.5\n").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn extraction_prompt_file_resolves_under_config_dir_and_expands_default() -> Result<()> {
+        let temp = TempDir::new()?;
+        fs::write(
+            temp.path().join("extract-prompt.md"),
+            "You index a team engineering wiki.\n{{default}}",
+        )?;
+        let config = parse_app_config("[extract]\nprompt_file = \"extract-prompt.md\"\n")?;
+
+        let prompt = load_extraction_prompt(temp.path(), &config.extract)?
+            .expect("expected prompt override");
+
+        assert!(prompt.starts_with("You index a team engineering wiki.\n"));
+        assert!(prompt.ends_with(EXTRACTION_SYSTEM_PROMPT));
+        assert!(load_extraction_prompt(temp.path(), &parse_app_config("")?.extract)?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_app_config_rejects_unknown_id_scheme() {
+        let error = match parse_app_config("[engine]\nid_scheme = \"v1\"\n") {
+            Ok(_) => panic!("expected id scheme to be rejected"),
+            Err(error) => error,
+        };
+
+        assert_eq!(error.to_string(), "invalid value for `engine.id_scheme`");
+        assert!(error
+            .chain()
+            .any(|cause| cause.to_string().contains("invalid id scheme")));
+    }
+
+    #[test]
+    fn parse_app_config_rejects_out_of_range_recency_weight() {
+        let error = match parse_app_config("[engine]\nrecency_weight = 1.5\n") {
+            Ok(_) => panic!("expected recency weight to be rejected"),
+            Err(error) => error,
+        };
+
+        assert!(error.chain().any(|cause| cause
+            .to_string()
+            .contains("recency_weight must be between 0.0 and 1.0")));
+    }
+
+    #[test]
+    fn parse_app_config_names_the_rejected_setting() {
+        let error = match parse_app_config("[extract]\nmin_confidence = 1.2\n") {
+            Ok(_) => panic!("expected min_confidence to be rejected"),
+            Err(error) => error,
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "invalid value for `extract.min_confidence`"
+        );
+        assert!(error
+            .chain()
+            .any(|cause| cause.to_string().contains("between 0.0 and 1.0, got 1.2")));
+        assert!(parse_app_config("[engine]\nmax_recall_limit = many\n").is_err());
+        assert!(parse_app_config("[rerank]\nrerank_provider = \"aliyun.\"\n").is_err());
+    }
+
     #[test]
     fn parse_providers_config_reads_timeout_and_concurrency_hints() -> Result<()> {
         let providers = parse_providers_config(
@@ -442,15 +897,126 @@ mod tests {
              [openai.embed]\nbase_url = \"https://api.openai.com/v1\"\nmodel = \"text-embedding-3-small\"\ndimension = 1536\ntimeout_ms = 1200\nmax_concurrent = 4\n",
         )?;
 
-        let embed = providers
-            .get("openai")
-            .and_then(|provider| provider.services.get("embed"))
-            .expect("expected openai.embed service");
+        let embed = providers.service("openai.embed")?;
         assert_eq!(embed.timeout_ms, Some(1200));
         assert_eq!(embed.max_concurrent, Some(4));
         Ok(())
     }
 
+    #[test]
+    fn parse_providers_config_interpolates_api_key_from_environment() -> Result<()> {
+        unsafe {
+            std::env::set_var("MEMO_TEST_INTERPOLATED_KEY", "sk-from-env");
+        }
+
+        let providers =
+            parse_providers_config("[openai]\napi_key = \"${MEMO_TEST_INTERPOLATED_KEY}\"\n")?;
+
+        assert_eq!(
+            providers.provider("openai")?.api_key("openai")?,
+            "sk-from-env"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn missing_api_key_variable_only_fails_the_provider_that_uses_it() -> Result<()> {
+        let providers = parse_providers_config(
+            "[openai]\napi_key = \"${MEMO_TEST_MISSING_KEY}\"\n[openai.embed]\nbase_url = \"https://api.openai.com/v1\"\nmodel = \"text-embedding-3-small\"\n\
+             [aliyun]\napi_key = \"sk-test\"\n[aliyun.rerank]\nbase_url = \"https://dashscope.aliyuncs.com/api/v1\"\nmodel = \"gte-rerank\"\n",
+        )?;
+
+        assert!(!providers.uses_placeholder_key("aliyun.rerank")?);
+        let error = providers
+            .uses_placeholder_key("openai.embed")
+            .expect_err("expected missing environment variable");
+        let message = format!("{error:#}");
+        assert!(message.contains("failed to resolve `openai.api_key`"));
+        assert!(message.contains("environment variable `MEMO_TEST_MISSING_KEY` is not set"));
+        Ok(())
+    }
+
+    #[test]
+    fn interpolate_env_keeps_plain_values_and_rejects_unterminated_references() -> Result<()> {
+        assert_eq!(interpolate_env("sk-test")?, "sk-test");
+        assert!(interpolate_env("${OPENAI_API_KEY").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_providers_config_reads_service_retry_settings() -> Result<()> {
+        let providers = parse_providers_config(
+            "[aliyun]\napi_key = \"sk-test\"\n\
+             [aliyun.rerank]\nbase_url = \"https://dashscope.aliyuncs.com/api/v1\"\nmodel = \"gte-rerank\"\nmax_retries = 3\nretry_backoff_ms = 1000\n",
+        )?;
+
+        let rerank = providers.service("aliyun.rerank")?;
+        assert_eq!(rerank.max_retries, Some(3));
+        assert_eq!(rerank.retry_backoff_ms, Some(1000));
+        Ok(())
+    }
+
+    #[test]
+    fn app_config_retry_settings_override_provider_service_defaults() -> Result<()> {
+        let temp = TempDir::new()?;
+        let config_dir = temp.path().join(".memo");
+        fs::create_dir_all(&config_dir)?;
+        fs::write(
+            config_dir.join("providers.toml"),
+            "[aliyun]\napi_key = \"sk-test\"\n[aliyun.rerank]\nbase_url = \"https://dashscope.aliyuncs.com/api/v1\"\nmodel = \"gte-rerank\"\nmax_retries = 3\nretry_backoff_ms = 1000\n",
+        )?;
+
+        let providers = ProvidersConfig::load(&config_dir)?;
+        let inherited = retry_policy(&providers, "aliyun.rerank", None, None)?;
+        let overridden = retry_policy(&providers, "aliyun.rerank", Some(1), None)?;
+
+        assert_eq!(inherited.max_retries, 3);
+        assert_eq!(inherited.retry_backoff_ms, 1000);
+        assert_eq!(overridden.max_retries, 1);
+        assert_eq!(overridden.retry_backoff_ms, 1000);
+        Ok(())
+    }
+
+    #[test]
+    fn provider_proxy_url_is_rejected_when_the_provider_is_resolved() -> Result<()> {
+        let providers = parse_providers_config(
+            "[openai]\napi_key = \"sk-test\"\nproxy_url = \"http://proxy.internal:3128\"\n\
+             [openai.embed]\nbase_url = \"https://api.openai.com/v1\"\nmodel = \"text-embedding-3-small\"\n",
+        )?;
+
+        let error = providers
+            .provider_config("openai.embed", "embedding")
+            .expect_err("expected proxy_url to be rejected");
+
+        assert!(format!("{error:#}").contains("`openai.proxy_url` is not supported"));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_providers_config_reads_embedding_document_prefix() -> Result<()> {
+        let providers = parse_providers_config(
+            "[ollama]\napi_key = \"\"\n\
+             [ollama.embed]\nbase_url = \"http://127.0.0.1:11434/v1\"\nmodel = \"multilingual-e5-large\"\ndocument_prefix = \"passage: \"\n",
+        )?;
+
+        let embed = providers.service("ollama.embed")?;
+        assert_eq!(embed.document_prefix.as_deref(), Some("passage: "));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_providers_config_reads_embedding_token_limit() -> Result<()> {
+        let providers = parse_providers_config(
+            "[openai]\napi_key = \"sk-test\"\n\
+             [openai.embed]\nbase_url = \"https://api.openai.com/v1\"\nmodel = \"text-embedding-3-small\"\nmax_tokens = 8191\ntruncate = true\n",
+        )?;
+
+        let embed = providers.service("openai.embed")?;
+        assert_eq!(embed.max_tokens, Some(8191));
+        assert_eq!(embed.truncate, Some(true));
+        Ok(())
+    }
+
     #[test]
     fn resolve_configured_data_dir_resolves_relative_path_against_fixed_config_dir() -> Result<()> {
         let temp = TempDir::new()?;
@@ -462,9 +1028,98 @@ mod tests {
         )?;
 
         let data_dir =
-            resolve_configured_data_dir(&config_dir)?.expect("expected configured data dir");
+            resolve_configured_data_dir(&config_dir, None)?.expect("expected configured data dir");
 
         assert_eq!(data_dir, config_dir.join("memory-data"));
         Ok(())
     }
+
+    #[test]
+    fn validate_providers_reports_placeholder_keys_without_calling_provider() -> Result<()> {
+        let temp = TempDir::new()?;
+        let config_dir = temp.path().join(".memo");
+        fs::create_dir_all(&config_dir)?;
+        fs::write(
+            config_dir.join("config.toml"),
+            "[embed]\nembedding_provider = \"openai.embed\"\n[rerank]\nrerank_provider = \"aliyun.rerank\"\n",
+        )?;
+        fs::write(
+            config_dir.join("providers.toml"),
+            "[openai]\napi_key = \"sk-your-openai-api-key\"\n[openai.embed]\nbase_url = \"https://api.openai.com/v1\"\nmodel = \"text-embedding-3-small\"\ndimension = 1536\n",
+        )?;
+
+        let checks = validate_providers(&config_dir, None)?;
+
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].capability, "embedding");
+        assert!(!checks[0].ok);
+        assert!(checks[0]
+            .detail
+            .as_deref()
+            .is_some_and(|detail| detail.contains("template placeholder")));
+        assert_eq!(checks[1].capability, "rerank");
+        assert!(!checks[1].ok);
+        assert!(checks[1]
+            .detail
+            .as_deref()
+            .is_some_and(|detail| detail.contains("provider `aliyun` not found")));
+        Ok(())
+    }
+
+    #[test]
+    fn validate_providers_requires_config_file() -> Result<()> {
+        let temp = TempDir::new()?;
+        let config_dir = temp.path().join(".memo");
+
+        let error =
+            validate_providers(&config_dir, None).expect_err("expected missing config error");
+
+        assert!(error.to_string().contains("run `memo awaken` first"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_provider_checks_every_service_under_the_provider() -> Result<()> {
+        let temp = TempDir::new()?;
+        let config_dir = temp.path().join(".memo");
+        fs::create_dir_all(&config_dir)?;
+        fs::write(
+            config_dir.join("providers.toml"),
+            "[openai]\napi_key = \"sk-your-openai-api-key\"\n[openai.embed]\nbase_url = \"https://api.openai.com/v1\"\nmodel = \"text-embedding-3-small\"\n[openai.extract]\nbase_url = \"https://api.openai.com/v1\"\nmodel = \"gpt-4o-mini\"\n[openai.vision]\nmodel = \"gpt-4o\"\n",
+        )?;
+
+        let checks = test_provider(&config_dir, None, "openai")?;
+
+        let refs = checks
+            .iter()
+            .map(|check| check.provider_ref.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            refs,
+            vec!["openai.embed", "openai.extract", "openai.vision"]
+        );
+        assert!(checks.iter().all(|check| !check.ok));
+        assert!(checks[2]
+            .detail
+            .as_deref()
+            .is_some_and(|detail| detail.contains("unknown service kind")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_provider_rejects_unknown_provider_name() -> Result<()> {
+        let temp = TempDir::new()?;
+        let config_dir = temp.path().join(".memo");
+        fs::create_dir_all(&config_dir)?;
+        fs::write(
+            config_dir.join("providers.toml"),
+            "[openai]\napi_key = \"sk-test\"\n",
+        )?;
+
+        let error =
+            test_provider(&config_dir, None, "aliyun").expect_err("expected unknown provider");
+
+        assert!(error.to_string().contains("provider `aliyun` not found"));
+        Ok(())
+    }
 }
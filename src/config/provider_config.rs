@@ -1,11 +1,14 @@
-use std::{collections::HashMap, fs, path::Path, time::Duration};
+use std::{collections::HashMap, fs, net::IpAddr, path::Path, time::Duration};
 
 use anyhow::{Context, Result};
 use lmkit::{Provider, ProviderConfig};
 
+/// `api_key` keeps its `${VAR}` references as written; they are expanded when the provider is
+/// resolved, so an unset variable only breaks the providers that use it.
 #[derive(Debug, Default)]
 pub(crate) struct ProviderEntry {
-    pub(crate) api_key: String,
+    api_key: String,
+    proxy_url: Option<String>,
     pub(crate) services: HashMap<String, ProviderService>,
 }
 
@@ -16,56 +19,90 @@ pub(crate) struct ProviderService {
     pub(crate) dimension: Option<usize>,
     pub(crate) timeout_ms: Option<u64>,
     pub(crate) max_concurrent: Option<usize>,
+    pub(crate) max_retries: Option<usize>,
+    pub(crate) retry_backoff_ms: Option<u64>,
+    pub(crate) document_prefix: Option<String>,
+    pub(crate) max_tokens: Option<usize>,
+    pub(crate) truncate: Option<bool>,
 }
 
-pub(crate) fn load_provider_config(
-    config_dir: &Path,
-    provider_ref: &str,
-    capability: &str,
-) -> Result<ProviderConfig> {
-    let providers_path = config_dir.join("providers.toml");
-    let providers_text = fs::read_to_string(&providers_path).with_context(|| {
-        format!(
-            "failed to read providers file: {}",
-            providers_path.display()
-        )
-    })?;
-
-    resolve_provider_config(&providers_text, provider_ref)
-        .with_context(|| format!("failed to resolve {capability} provider `{provider_ref}`"))
+/// Parsed `providers.toml`. A command reads it once and resolves every capability from it.
+#[derive(Debug, Default)]
+pub(crate) struct ProvidersConfig {
+    providers: HashMap<String, ProviderEntry>,
 }
 
-pub(crate) fn provider_ref_uses_placeholder_key(
-    config_dir: &Path,
-    provider_ref: &str,
-) -> Result<bool> {
-    let providers_path = config_dir.join("providers.toml");
-    let providers_text = fs::read_to_string(&providers_path).with_context(|| {
-        format!(
-            "failed to read providers file: {}",
-            providers_path.display()
-        )
-    })?;
-    provider_ref_uses_placeholder_key_from_text(&providers_text, provider_ref)
-}
+impl ProvidersConfig {
+    pub(crate) fn load(config_dir: &Path) -> Result<Self> {
+        let providers_path = config_dir.join("providers.toml");
+        let providers_text = fs::read_to_string(&providers_path).with_context(|| {
+            format!(
+                "failed to read providers file: {}",
+                providers_path.display()
+            )
+        })?;
+        parse_providers_config(&providers_text).context("failed to parse providers.toml")
+    }
+
+    pub(crate) fn provider_count(&self) -> usize {
+        self.providers.len()
+    }
+
+    pub(crate) fn provider(&self, provider_name: &str) -> Result<&ProviderEntry> {
+        self.providers
+            .get(provider_name)
+            .with_context(|| format!("provider `{provider_name}` not found"))
+    }
+
+    pub(crate) fn service(&self, provider_ref: &str) -> Result<&ProviderService> {
+        let (provider_name, service_name) = split_provider_ref(provider_ref)?;
+        self.provider(provider_name)?
+            .services
+            .get(service_name)
+            .with_context(|| format!("service `{service_name}` not found under `{provider_name}`"))
+    }
+
+    pub(crate) fn provider_config(
+        &self,
+        provider_ref: &str,
+        capability: &str,
+    ) -> Result<ProviderConfig> {
+        split_provider_ref(provider_ref)
+            .and_then(|(provider_name, service_name)| {
+                self.provider(provider_name)?
+                    .provider_config(provider_name, service_name)
+            })
+            .with_context(|| format!("failed to resolve {capability} provider `{provider_ref}`"))
+    }
 
-pub(crate) fn provider_ref_uses_placeholder_key_from_text(
-    providers_toml: &str,
-    provider_ref: &str,
-) -> Result<bool> {
-    let providers =
-        parse_providers_config(providers_toml).context("failed to parse providers.toml")?;
-    let (provider_name, _) = split_provider_ref(provider_ref)?;
-    let provider_entry = providers
-        .get(provider_name)
-        .with_context(|| format!("provider `{provider_name}` not found"))?;
-    Ok(is_placeholder_api_key(
-        provider_name,
-        &provider_entry.api_key,
-    ))
+    pub(crate) fn service_names(&self, provider_name: &str) -> Result<Vec<String>> {
+        let mut service_names = self
+            .provider(provider_name)?
+            .services
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        service_names.sort_unstable();
+        Ok(service_names)
+    }
+
+    pub(crate) fn uses_placeholder_key(&self, provider_ref: &str) -> Result<bool> {
+        let (provider_name, service_name) = split_provider_ref(provider_ref)?;
+        let provider_entry = self.provider(provider_name)?;
+        let api_key = provider_entry.api_key(provider_name)?;
+        let is_local = provider_entry
+            .services
+            .get(service_name)
+            .and_then(|service| service.base_url.as_deref())
+            .is_some_and(is_local_base_url);
+        if is_local && api_key.trim().is_empty() {
+            return Ok(false);
+        }
+        Ok(is_placeholder_api_key(provider_name, &api_key))
+    }
 }
 
-pub(crate) fn is_placeholder_api_key(provider_name: &str, api_key: &str) -> bool {
+fn is_placeholder_api_key(provider_name: &str, api_key: &str) -> bool {
     let key = api_key.trim();
     (key.is_empty() && provider_name != "ollama")
         || key.contains("your-")
@@ -75,38 +112,71 @@ pub(crate) fn is_placeholder_api_key(provider_name: &str, api_key: &str) -> bool
         || key == format!("sk-your-{provider_name}-api-key")
 }
 
-fn resolve_provider_config(providers_toml: &str, provider_ref: &str) -> Result<ProviderConfig> {
-    let providers =
-        parse_providers_config(providers_toml).context("failed to parse providers.toml")?;
-    let (provider_name, service_name) = split_provider_ref(provider_ref)?;
-    let provider_entry = providers
-        .get(provider_name)
-        .with_context(|| format!("provider `{provider_name}` not found"))?;
-    let service_entry = provider_entry
-        .services
-        .get(service_name)
-        .with_context(|| format!("service `{service_name}` not found under `{provider_name}`"))?;
-
-    let provider: Provider = provider_name.parse()?;
-    let base_url = service_entry
-        .base_url
-        .clone()
-        .with_context(|| "missing `base_url`".to_string())?;
-    let model = service_entry
-        .model
-        .clone()
-        .with_context(|| "missing `model`".to_string())?;
-
-    let mut config =
-        ProviderConfig::with_base_url(provider, &provider_entry.api_key, base_url, model);
-    config.dimension = service_entry.dimension;
-    config.timeout = service_entry.timeout_ms.map(Duration::from_millis);
-    config.max_concurrent = service_entry.max_concurrent;
-
-    Ok(config)
+impl ProviderEntry {
+    pub(crate) fn api_key(&self, provider_name: &str) -> Result<String> {
+        interpolate_env(&self.api_key)
+            .with_context(|| format!("failed to resolve `{provider_name}.api_key`"))
+    }
+
+    /// Builds the lmkit client config for one of this provider's services.
+    fn provider_config(&self, provider_name: &str, service_name: &str) -> Result<ProviderConfig> {
+        let service = self.services.get(service_name).with_context(|| {
+            format!("service `{service_name}` not found under `{provider_name}`")
+        })?;
+
+        // lmkit builds its HTTP clients without a per-client proxy setting, and exporting the
+        // proxy into the process environment would leak it into every other provider.
+        if self.proxy_url.is_some() {
+            anyhow::bail!(
+                "`{provider_name}.proxy_url` is not supported; set HTTPS_PROXY/HTTP_PROXY instead"
+            );
+        }
+
+        let provider: Provider = provider_name.parse()?;
+        let (Some(base_url), Some(model)) = (service.base_url.clone(), service.model.clone())
+        else {
+            let missing = [
+                ("`base_url`", &service.base_url),
+                ("`model`", &service.model),
+            ]
+            .into_iter()
+            .filter_map(|(field, value)| value.is_none().then_some(field))
+            .collect::<Vec<_>>();
+            anyhow::bail!("missing {}", missing.join(", "));
+        };
+
+        let api_key = self.api_key(provider_name)?;
+        let mut config = ProviderConfig::with_base_url(provider, &api_key, base_url, model);
+        config.dimension = service.dimension;
+        config.timeout = service.timeout_ms.map(Duration::from_millis);
+        config.max_concurrent = service.max_concurrent;
+        Ok(config)
+    }
+}
+
+/// Local servers (Ollama, vLLM, LM Studio, ...) usually run without an API key.
+fn is_local_base_url(base_url: &str) -> bool {
+    let authority = base_url
+        .split_once("://")
+        .map_or(base_url, |(_, rest)| rest)
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or_default();
+    let authority = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    // IPv6 literals are bracketed (`[::1]:11434`); anything else ends at the port separator.
+    let host = match authority.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => authority.split(':').next().unwrap_or_default(),
+    };
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback() || ip.is_unspecified())
 }
 
-fn split_provider_ref(provider_ref: &str) -> Result<(&str, &str)> {
+pub(crate) fn split_provider_ref(provider_ref: &str) -> Result<(&str, &str)> {
     let (provider_name, service_name) = provider_ref.split_once('.').with_context(|| {
         format!("provider ref `{provider_ref}` must look like `<provider>.<service>`")
     })?;
@@ -118,7 +188,30 @@ fn split_provider_ref(provider_ref: &str) -> Result<(&str, &str)> {
     Ok((provider_name, service_name))
 }
 
-pub(crate) fn parse_providers_config(contents: &str) -> Result<HashMap<String, ProviderEntry>> {
+/// Replaces every `${VAR}` in `value` with the contents of the environment variable `VAR`.
+pub(crate) fn interpolate_env(value: &str) -> Result<String> {
+    let mut resolved = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        resolved.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .with_context(|| format!("unterminated `${{` in `{value}`"))?;
+        let name = &after[..end];
+        if name.is_empty() {
+            anyhow::bail!("empty environment variable reference in `{value}`");
+        }
+        let env_value = std::env::var(name)
+            .with_context(|| format!("environment variable `{name}` is not set"))?;
+        resolved.push_str(&env_value);
+        rest = &after[end + 1..];
+    }
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+pub(crate) fn parse_providers_config(contents: &str) -> Result<ProvidersConfig> {
     enum Section {
         Provider(String),
         Service { provider: String, service: String },
@@ -157,11 +250,9 @@ pub(crate) fn parse_providers_config(contents: &str) -> Result<HashMap<String, P
             Some(Section::Provider(provider)) => {
                 let entry = providers.entry(provider.clone()).or_default();
                 if key == "api_key" {
-                    entry.api_key = value
-                        .strip_prefix('"')
-                        .and_then(|item| item.strip_suffix('"'))
-                        .with_context(|| format!("expected quoted string, got `{value}`"))?
-                        .to_string();
+                    entry.api_key = parse_quoted(value)?.to_string();
+                } else if key == "proxy_url" {
+                    entry.proxy_url = Some(parse_quoted(value)?.to_string());
                 }
             }
             Some(Section::Service { provider, service }) => {
@@ -172,29 +263,28 @@ pub(crate) fn parse_providers_config(contents: &str) -> Result<HashMap<String, P
                     .entry(service.clone())
                     .or_default();
                 match key {
-                    "base_url" => {
-                        service_entry.base_url = Some(
-                            value
-                                .strip_prefix('"')
-                                .and_then(|item| item.strip_suffix('"'))
-                                .with_context(|| format!("expected quoted string, got `{value}`"))?
-                                .to_string(),
-                        )
-                    }
-                    "model" => {
-                        service_entry.model = Some(
-                            value
-                                .strip_prefix('"')
-                                .and_then(|item| item.strip_suffix('"'))
-                                .with_context(|| format!("expected quoted string, got `{value}`"))?
-                                .to_string(),
-                        )
+                    "base_url" => service_entry.base_url = Some(parse_quoted(value)?.to_string()),
+                    "model" => service_entry.model = Some(parse_quoted(value)?.to_string()),
+                    "document_prefix" => {
+                        service_entry.document_prefix = Some(parse_quoted(value)?.to_string())
                     }
                     "dimension" => service_entry.dimension = Some(value.parse::<usize>()?),
                     "timeout_ms" => service_entry.timeout_ms = Some(value.parse::<u64>()?),
                     "max_concurrent" => {
                         service_entry.max_concurrent = Some(value.parse::<usize>()?)
                     }
+                    "max_tokens" => service_entry.max_tokens = Some(value.parse::<usize>()?),
+                    "truncate" => {
+                        service_entry.truncate = Some(match value {
+                            "true" => true,
+                            "false" => false,
+                            _ => anyhow::bail!("expected bool, got `{value}`"),
+                        })
+                    }
+                    "max_retries" => service_entry.max_retries = Some(value.parse::<usize>()?),
+                    "retry_backoff_ms" => {
+                        service_entry.retry_backoff_ms = Some(value.parse::<u64>()?)
+                    }
                     _ => {}
                 }
             }
@@ -204,5 +294,12 @@ pub(crate) fn parse_providers_config(contents: &str) -> Result<HashMap<String, P
         }
     }
 
-    Ok(providers)
+    Ok(ProvidersConfig { providers })
+}
+
+fn parse_quoted(value: &str) -> Result<&str> {
+    value
+        .strip_prefix('"')
+        .and_then(|item| item.strip_suffix('"'))
+        .with_context(|| format!("expected quoted string, got `{value}`"))
 }
@@ -0,0 +1,228 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+const SAVED_RECALLS_FILE: &str = "saved-recalls.toml";
+const SAVED_RECALL_TABLE: &str = "[[recall]]";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct SavedRecall {
+    pub(crate) name: String,
+    pub(crate) query: String,
+    pub(crate) limit: usize,
+    pub(crate) deep: bool,
+    pub(crate) boost_recent: bool,
+}
+
+pub(crate) fn load_saved_recalls(config_dir: &Path) -> Result<Vec<SavedRecall>> {
+    let path = config_dir.join(SAVED_RECALLS_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read saved recalls: {}", path.display()))?;
+    parse_saved_recalls(&contents)
+        .with_context(|| format!("failed to parse saved recalls: {}", path.display()))
+}
+
+/// Adds `recall` to the saved recall file, replacing any entry with the same name. Returns
+/// whether an existing entry was replaced.
+pub(crate) fn save_recall(config_dir: &Path, recall: SavedRecall) -> Result<bool> {
+    let mut recalls = load_saved_recalls(config_dir)?;
+    let replaced = match recalls.iter_mut().find(|saved| saved.name == recall.name) {
+        Some(saved) => {
+            *saved = recall;
+            true
+        }
+        None => {
+            recalls.push(recall);
+            false
+        }
+    };
+
+    let path = config_dir.join(SAVED_RECALLS_FILE);
+    fs::create_dir_all(config_dir)
+        .with_context(|| format!("failed to create config dir: {}", config_dir.display()))?;
+    fs::write(&path, render_saved_recalls(&recalls))
+        .with_context(|| format!("failed to write saved recalls: {}", path.display()))?;
+    Ok(replaced)
+}
+
+pub(crate) fn find_saved_recall(config_dir: &Path, name: &str) -> Result<SavedRecall> {
+    load_saved_recalls(config_dir)?
+        .into_iter()
+        .find(|saved| saved.name == name)
+        .with_context(|| format!("no saved recall named `{name}`"))
+}
+
+fn parse_saved_recalls(contents: &str) -> Result<Vec<SavedRecall>> {
+    let mut recalls = Vec::new();
+    let mut current: Option<PartialRecall> = None;
+
+    for (line_no, raw_line) in contents.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line == SAVED_RECALL_TABLE {
+            if let Some(partial) = current.take() {
+                recalls.push(partial.finish()?);
+            }
+            current = Some(PartialRecall::default());
+            continue;
+        }
+
+        let partial = current.as_mut().with_context(|| {
+            format!(
+                "line {}: expected `{SAVED_RECALL_TABLE}` before keys",
+                line_no + 1
+            )
+        })?;
+        let (key, value) = line
+            .split_once('=')
+            .with_context(|| format!("line {}: expected `key = value`", line_no + 1))?;
+        let value = value.trim();
+        match key.trim() {
+            "name" => partial.name = Some(parse_quoted(value)?),
+            "query" => partial.query = Some(parse_quoted(value)?),
+            "limit" => partial.limit = Some(value.parse::<usize>()?),
+            "deep" => partial.deep = value.parse::<bool>()?,
+            "boost_recent" => partial.boost_recent = value.parse::<bool>()?,
+            _ => {}
+        }
+    }
+
+    if let Some(partial) = current {
+        recalls.push(partial.finish()?);
+    }
+    Ok(recalls)
+}
+
+fn render_saved_recalls(recalls: &[SavedRecall]) -> String {
+    recalls
+        .iter()
+        .map(|recall| {
+            format!(
+                "{SAVED_RECALL_TABLE}\nname = {}\nquery = {}\nlimit = {}\ndeep = {}\nboost_recent = {}\n",
+                quote(&recall.name),
+                quote(&recall.query),
+                recall.limit,
+                recall.deep,
+                recall.boost_recent,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Default)]
+struct PartialRecall {
+    name: Option<String>,
+    query: Option<String>,
+    limit: Option<usize>,
+    deep: bool,
+    boost_recent: bool,
+}
+
+impl PartialRecall {
+    fn finish(self) -> Result<SavedRecall> {
+        let name = self.name.context("saved recall is missing `name`")?;
+        let query = self
+            .query
+            .with_context(|| format!("saved recall `{name}` is missing `query`"))?;
+        Ok(SavedRecall {
+            name,
+            query,
+            limit: self.limit.unwrap_or(10),
+            deep: self.deep,
+            boost_recent: self.boost_recent,
+        })
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn parse_quoted(value: &str) -> Result<String> {
+    let inner = value
+        .strip_prefix('"')
+        .and_then(|item| item.strip_suffix('"'))
+        .with_context(|| format!("expected quoted string, got `{value}`"))?;
+    let mut parsed = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            match chars.next() {
+                Some(escaped @ ('\\' | '"')) => parsed.push(escaped),
+                _ => anyhow::bail!("unsupported escape in `{value}`"),
+            }
+        } else {
+            parsed.push(ch);
+        }
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    use super::{find_saved_recall, load_saved_recalls, save_recall, SavedRecall};
+
+    fn saved(name: &str, query: &str) -> SavedRecall {
+        SavedRecall {
+            name: name.to_string(),
+            query: query.to_string(),
+            limit: 5,
+            deep: false,
+            boost_recent: true,
+        }
+    }
+
+    #[test]
+    fn saved_recalls_round_trip_quotes_and_hashes() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let recall = saved("alice", r#"where did "Alice" move #travel \ notes"#);
+
+        assert!(!save_recall(temp_dir.path(), recall.clone())?);
+
+        assert_eq!(load_saved_recalls(temp_dir.path())?, vec![recall.clone()]);
+        assert_eq!(find_saved_recall(temp_dir.path(), "alice")?, recall);
+        Ok(())
+    }
+
+    #[test]
+    fn saving_existing_name_replaces_entry() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        save_recall(temp_dir.path(), saved("alice", "Alice"))?;
+        save_recall(temp_dir.path(), saved("bob", "Bob"))?;
+
+        assert!(save_recall(
+            temp_dir.path(),
+            saved("alice", "Alice in Paris")
+        )?);
+
+        let recalls = load_saved_recalls(temp_dir.path())?;
+        assert_eq!(recalls.len(), 2);
+        assert_eq!(recalls[0].query, "Alice in Paris");
+        assert_eq!(recalls[1].name, "bob");
+        Ok(())
+    }
+
+    #[test]
+    fn missing_saved_recall_reports_name() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+
+        let error = find_saved_recall(temp_dir.path(), "missing").expect_err("expected error");
+
+        assert!(error
+            .to_string()
+            .contains("no saved recall named `missing`"));
+        Ok(())
+    }
+}
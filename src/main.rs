@@ -1,18 +1,47 @@
-use anyhow::Result;
+use std::{fs::OpenOptions, sync::Mutex};
+
+use anyhow::{Context, Result};
 use clap::Parser;
 
+use crate::cli::args::{Cli, LogFormat};
+
 mod cli;
 mod config;
 mod providers;
 
 fn main() -> Result<()> {
-    tracing_subscriber::fmt()
+    let cli = Cli::parse();
+    init_tracing(&cli)?;
+
+    cli::commands::run(cli)
+}
+
+fn init_tracing(cli: &Cli) -> Result<()> {
+    let builder = tracing_subscriber::fmt()
         .with_env_filter(
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("warn")),
         )
         .with_target(false)
-        .init();
+        .with_ansi(cli.use_color() && cli.log_file.is_none());
+
+    let log_file = cli
+        .log_file
+        .as_ref()
+        .map(|path| {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("failed to open log file {}", path.display()))
+        })
+        .transpose()?;
 
-    cli::commands::run(cli::args::Cli::parse())
+    match (cli.log_format, log_file) {
+        (LogFormat::Text, None) => builder.init(),
+        (LogFormat::Text, Some(file)) => builder.with_writer(Mutex::new(file)).init(),
+        (LogFormat::Json, None) => builder.json().init(),
+        (LogFormat::Json, Some(file)) => builder.json().with_writer(Mutex::new(file)).init(),
+    }
+    Ok(())
 }
@@ -2,15 +2,30 @@ use anyhow::{Context, Result};
 use lmkit::{create_embed_provider, EmbedProvider as LmkitEmbedProvider, ProviderConfig};
 use memo_engine::EmbeddingProvider;
 use tokio::runtime::{Builder, Runtime};
+use tracing::warn;
+
+/// Rough bytes-per-token ratio used to estimate whether content fits the model window.
+const ESTIMATED_BYTES_PER_TOKEN: usize = 4;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct EmbeddingOptions {
+    pub(crate) document_prefix: Option<String>,
+    pub(crate) max_tokens: Option<usize>,
+    pub(crate) truncate: bool,
+}
 
 pub(crate) struct LmkitEmbeddingAdapter {
     runtime: Runtime,
     provider: Box<dyn LmkitEmbedProvider>,
     dimension: usize,
+    options: EmbeddingOptions,
 }
 
 impl LmkitEmbeddingAdapter {
-    pub(crate) fn new(config: ProviderConfig) -> Result<Self> {
+    pub(crate) fn new_with_options(
+        config: ProviderConfig,
+        options: EmbeddingOptions,
+    ) -> Result<Self> {
         let runtime = Builder::new_multi_thread()
             .enable_all()
             .build()
@@ -23,8 +38,17 @@ impl LmkitEmbeddingAdapter {
             runtime,
             provider,
             dimension,
+            options,
         })
     }
+
+    fn encode(&self, text: &str) -> Result<Vec<f32>> {
+        let input = with_prefix(self.options.document_prefix.as_deref(), text);
+        let input = fit_token_limit(&input, self.options.max_tokens, self.options.truncate);
+        self.runtime
+            .block_on(self.provider.encode(input))
+            .context("lmkit embed request failed")
+    }
 }
 
 impl EmbeddingProvider for LmkitEmbeddingAdapter {
@@ -33,8 +57,83 @@ impl EmbeddingProvider for LmkitEmbeddingAdapter {
     }
 
     fn embed_text(&self, text: &str) -> Result<Vec<f32>> {
-        self.runtime
-            .block_on(self.provider.encode(text))
-            .context("lmkit embed request failed")
+        self.encode(text)
+    }
+}
+
+fn with_prefix(prefix: Option<&str>, text: &str) -> String {
+    match prefix {
+        Some(prefix) if !prefix.is_empty() => format!("{prefix}{text}"),
+        _ => text.to_string(),
+    }
+}
+
+fn fit_token_limit(text: &str, max_tokens: Option<usize>, truncate: bool) -> &str {
+    let Some(max_tokens) = max_tokens else {
+        return text;
+    };
+    let estimated_tokens = text.len() / ESTIMATED_BYTES_PER_TOKEN;
+    if estimated_tokens <= max_tokens {
+        return text;
+    }
+    if !truncate {
+        warn!(
+            estimated_tokens,
+            max_tokens, "embedding input may exceed the model token limit and be truncated"
+        );
+        return text;
+    }
+
+    let mut end = max_tokens.saturating_mul(ESTIMATED_BYTES_PER_TOKEN);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    warn!(
+        estimated_tokens,
+        max_tokens, "truncating embedding input to the model token limit"
+    );
+    &text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fit_token_limit, with_prefix};
+
+    #[test]
+    fn with_prefix_prepends_configured_prefix_verbatim() {
+        assert_eq!(
+            with_prefix(Some("passage: "), "alice lives in paris"),
+            "passage: alice lives in paris"
+        );
+        assert_eq!(with_prefix(Some(""), "alice"), "alice");
+        assert_eq!(with_prefix(None, "alice"), "alice");
+    }
+
+    #[test]
+    fn fit_token_limit_only_truncates_when_enabled() {
+        let text = "a".repeat(40);
+
+        assert_eq!(fit_token_limit(&text, None, true).len(), 40);
+        assert_eq!(fit_token_limit(&text, Some(5), false).len(), 40);
+        assert_eq!(fit_token_limit(&text, Some(5), true).len(), 20);
+    }
+
+    #[test]
+    fn fit_token_limit_counts_the_prefix_against_the_limit() {
+        let input = with_prefix(Some("passage: "), &"a".repeat(40));
+
+        let truncated = fit_token_limit(&input, Some(5), true);
+
+        assert_eq!(truncated.len(), 20);
+        assert!(truncated.starts_with("passage: "));
+    }
+
+    #[test]
+    fn fit_token_limit_truncates_on_char_boundary() {
+        let text = "记".repeat(10);
+
+        let truncated = fit_token_limit(&text, Some(2), true);
+
+        assert_eq!(truncated, "记".repeat(2));
     }
 }
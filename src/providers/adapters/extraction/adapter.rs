@@ -10,16 +10,32 @@ use super::{
     parse_extraction_response_with_options, ExtractionCleanupOptions, EXTRACTION_SYSTEM_PROMPT,
 };
 
+/// Extraction output is parsed as JSON, so sampling stays deterministic unless configured.
+pub(crate) const DEFAULT_EXTRACTION_TEMPERATURE: f32 = 0.0;
+
+/// Request-side settings, kept apart from the `Copy` cleanup options applied to responses.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ExtractionRequestOptions {
+    /// Extra system message sent ahead of the built-in extraction prompt, e.g. a persona or
+    /// domain description.
+    pub(crate) system_prompt: Option<String>,
+    /// Replacement for the built-in extraction prompt.
+    pub(crate) extraction_prompt: Option<String>,
+    pub(crate) temperature: f32,
+}
+
 pub(crate) struct LmkitExtractionAdapter {
     runtime: Runtime,
     provider: Box<dyn LmkitChatProvider>,
     options: ExtractionCleanupOptions,
+    request_options: ExtractionRequestOptions,
 }
 
 impl LmkitExtractionAdapter {
     pub(crate) fn new_with_options(
         config: ProviderConfig,
         options: ExtractionCleanupOptions,
+        request_options: ExtractionRequestOptions,
     ) -> Result<Self> {
         let runtime = Builder::new_multi_thread()
             .enable_all()
@@ -32,20 +48,29 @@ impl LmkitExtractionAdapter {
             runtime,
             provider,
             options,
+            request_options,
         })
     }
 }
 
 impl ExtractionProvider for LmkitExtractionAdapter {
     fn extract(&self, text: &str) -> Result<ExtractionResult> {
+        let mut messages = Vec::with_capacity(3);
+        if let Some(system_prompt) = self.request_options.system_prompt.as_deref() {
+            messages.push(ChatMessage::system(system_prompt));
+        }
+        messages.push(ChatMessage::system(
+            self.request_options
+                .extraction_prompt
+                .as_deref()
+                .unwrap_or(EXTRACTION_SYSTEM_PROMPT),
+        ));
+        messages.push(ChatMessage::user(text));
         let request = ChatRequest {
-            messages: vec![
-                ChatMessage::system(EXTRACTION_SYSTEM_PROMPT),
-                ChatMessage::user(text),
-            ],
+            messages,
             response_format: Some(ResponseFormat::JsonObject),
             preset: Some(RequestPreset::Execution),
-            temperature: Some(0.0),
+            temperature: Some(self.request_options.temperature),
             ..Default::default()
         };
         let response = self
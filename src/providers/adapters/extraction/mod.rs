@@ -2,15 +2,31 @@ mod adapter;
 mod normalize;
 mod prompt;
 
-pub(crate) use adapter::LmkitExtractionAdapter;
+pub(crate) use adapter::{
+    ExtractionRequestOptions, LmkitExtractionAdapter, DEFAULT_EXTRACTION_TEMPERATURE,
+};
 pub(crate) use normalize::{parse_extraction_response_with_options, ExtractionCleanupOptions};
-pub(crate) use prompt::EXTRACTION_SYSTEM_PROMPT;
+pub(crate) use prompt::{render_template, EXTRACTION_SYSTEM_PROMPT};
 
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
 
-    use super::{parse_extraction_response_with_options, ExtractionCleanupOptions};
+    use std::collections::HashMap;
+
+    use super::{
+        parse_extraction_response_with_options, render_template, ExtractionCleanupOptions,
+    };
+
+    #[test]
+    fn render_template_fills_known_placeholders_and_keeps_unknown_ones() {
+        let vars = HashMap::from([("default", "BUILT-IN")]);
+
+        assert_eq!(
+            render_template("Team wiki.\n{{ default }}\n{{missing}} {{open", &vars),
+            "Team wiki.\nBUILT-IN\n{{missing}} {{open"
+        );
+    }
 
     #[test]
     fn parse_extraction_response_reads_entities_and_facts() -> Result<()> {
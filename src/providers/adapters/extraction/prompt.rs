@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 pub(crate) const EXTRACTION_SYSTEM_PROMPT: &str = r#"You extract memory facts from user text.
 Return strict JSON only.
 
@@ -28,3 +30,26 @@ Rules:
 - Use concise canonical names.
 - Facts must be directly supported by the input text.
 "#;
+
+/// Replaces each `{{name}}` with `vars[name]`. Unknown placeholders are left untouched so a
+/// typo stays visible in the rendered prompt.
+pub(crate) fn render_template(template: &str, vars: &HashMap<&str, &str>) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        let name = after_open[..end].trim();
+        match vars.get(name) {
+            Some(value) => rendered.push_str(value),
+            None => rendered.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after_open[end + 2..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
@@ -2,14 +2,20 @@ use anyhow::{Context, Result};
 use lmkit::{create_rerank_provider, ProviderConfig};
 use memo_engine::{RerankProvider, RerankScore};
 use tokio::runtime::{Builder, Runtime};
+use tracing::debug;
+
+/// Providers whose raw rerank scores cluster too tightly to rank by and are min-max normalized.
+const NORMALIZED_SCORE_PROVIDERS: &[&str] = &["zhipu"];
+const NORMALIZE_EPSILON: f32 = 1e-6;
 
 pub(crate) struct LmkitRerankAdapter {
     runtime: Runtime,
     provider: Box<dyn lmkit::RerankProvider>,
+    normalize_scores: bool,
 }
 
 impl LmkitRerankAdapter {
-    pub(crate) fn new(config: ProviderConfig) -> Result<Self> {
+    pub(crate) fn new(config: ProviderConfig, provider_ref: &str) -> Result<Self> {
         let runtime = Builder::new_multi_thread()
             .enable_all()
             .build()
@@ -17,7 +23,15 @@ impl LmkitRerankAdapter {
         let provider =
             create_rerank_provider(&config).context("failed to create lmkit rerank provider")?;
 
-        Ok(Self { runtime, provider })
+        let normalize_scores = provider_ref
+            .split_once('.')
+            .is_some_and(|(provider_name, _)| NORMALIZED_SCORE_PROVIDERS.contains(&provider_name));
+
+        Ok(Self {
+            runtime,
+            provider,
+            normalize_scores,
+        })
     }
 }
 
@@ -29,12 +43,86 @@ impl RerankProvider for LmkitRerankAdapter {
             .block_on(self.provider.rerank(query, &refs, Some(refs.len())))
             .context("lmkit rerank request failed")?;
 
-        Ok(items
+        let mut scores = items
             .into_iter()
             .map(|item| RerankScore {
                 index: item.index,
                 score: item.score as f32,
             })
-            .collect())
+            .collect::<Vec<_>>();
+        if self.normalize_scores {
+            normalize_scores(&mut scores);
+        }
+        Ok(scores)
+    }
+}
+
+/// Min-max scales scores into `[0, 1)`. When every score is equal, the provider's result order
+/// is kept instead, with scores falling linearly from `1.0`.
+fn normalize_scores(scores: &mut [RerankScore]) {
+    if scores.is_empty() {
+        return;
+    }
+    let min = scores
+        .iter()
+        .map(|item| item.score)
+        .fold(f32::INFINITY, f32::min);
+    let max = scores
+        .iter()
+        .map(|item| item.score)
+        .fold(f32::NEG_INFINITY, f32::max);
+
+    if max == min {
+        let count = scores.len() as f32;
+        for (rank, item) in scores.iter_mut().enumerate() {
+            item.score = 1.0 - rank as f32 / count;
+        }
+        debug!(
+            count = scores.len(),
+            "rerank scores were identical; using positional scores"
+        );
+    } else {
+        for item in scores.iter_mut() {
+            item.score = (item.score - min) / (max - min + NORMALIZE_EPSILON);
+        }
+        debug!(
+            count = scores.len(),
+            min, max, "min-max normalized rerank scores"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use memo_engine::RerankScore;
+
+    use super::normalize_scores;
+
+    fn score(index: usize, score: f32) -> RerankScore {
+        RerankScore { index, score }
+    }
+
+    #[test]
+    fn normalize_scores_spreads_clustered_scores() {
+        let mut scores = vec![score(2, 0.999), score(0, 0.997), score(1, 0.995)];
+
+        normalize_scores(&mut scores);
+
+        assert!(scores[0].score > 0.99);
+        assert!((scores[1].score - 0.5).abs() < 0.01);
+        assert_eq!(scores[2].score, 0.0);
+        assert_eq!(scores[0].index, 2);
+    }
+
+    #[test]
+    fn normalize_scores_falls_back_to_positions_for_equal_scores() {
+        let mut scores = vec![score(1, 1.0), score(0, 1.0), score(2, 1.0), score(3, 1.0)];
+
+        normalize_scores(&mut scores);
+
+        assert_eq!(
+            scores.iter().map(|item| item.score).collect::<Vec<_>>(),
+            vec![1.0, 0.75, 0.5, 0.25]
+        );
     }
 }
@@ -0,0 +1,153 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use memo_engine::{EmbeddingProvider, ExtractionProvider, RerankProvider};
+use serde::Serialize;
+
+const EMBEDDING_PROBE_TEXT: &str = "ping";
+const EXTRACTION_PROBE_TEXT: &str = "Alice lives in Paris.";
+const RERANK_PROBE_QUERY: &str = "ping";
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub(crate) struct ProviderCheck {
+    pub capability: String,
+    pub provider_ref: String,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl ProviderCheck {
+    pub(crate) fn from_result(
+        capability: &str,
+        provider_ref: &str,
+        result: Result<Duration>,
+    ) -> Self {
+        match result {
+            Ok(latency) => Self {
+                capability: capability.to_string(),
+                provider_ref: provider_ref.to_string(),
+                ok: true,
+                latency_ms: Some(latency.as_millis() as u64),
+                detail: None,
+            },
+            Err(error) => Self {
+                capability: capability.to_string(),
+                provider_ref: provider_ref.to_string(),
+                ok: false,
+                latency_ms: None,
+                detail: Some(format!("{error:#}")),
+            },
+        }
+    }
+}
+
+/// Sends one short string through the embedding provider and returns the round-trip time.
+pub(crate) fn check_embedding(provider: &dyn EmbeddingProvider) -> Result<Duration> {
+    let started = Instant::now();
+    let vector = provider.embed_text(EMBEDDING_PROBE_TEXT)?;
+    let elapsed = started.elapsed();
+    if vector.len() != provider.dimension() {
+        anyhow::bail!(
+            "embedding dimension mismatch: configured {}, provider returned {}",
+            provider.dimension(),
+            vector.len()
+        );
+    }
+    Ok(elapsed)
+}
+
+/// Sends a minimal prompt through the extraction provider and returns the round-trip time.
+pub(crate) fn check_extraction(provider: &dyn ExtractionProvider) -> Result<Duration> {
+    let started = Instant::now();
+    provider
+        .extract(EXTRACTION_PROBE_TEXT)
+        .context("extraction probe failed")?;
+    Ok(started.elapsed())
+}
+
+/// Sends a one-word query with a single document through the rerank provider and returns the
+/// round-trip time.
+pub(crate) fn check_rerank(provider: &dyn RerankProvider) -> Result<Duration> {
+    let started = Instant::now();
+    provider
+        .rerank(RERANK_PROBE_QUERY, &[RERANK_PROBE_QUERY.to_string()])
+        .context("rerank probe failed")?;
+    Ok(started.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use memo_engine::{EmbeddingProvider, RerankProvider, RerankScore};
+
+    use super::{check_embedding, check_rerank, ProviderCheck};
+
+    struct FixedEmbeddingProvider {
+        dimension: usize,
+        returned: usize,
+    }
+
+    impl EmbeddingProvider for FixedEmbeddingProvider {
+        fn dimension(&self) -> usize {
+            self.dimension
+        }
+
+        fn embed_text(&self, _text: &str) -> Result<Vec<f32>> {
+            Ok(vec![0.0; self.returned])
+        }
+    }
+
+    struct UnavailableRerankProvider;
+
+    impl RerankProvider for UnavailableRerankProvider {
+        fn rerank(&self, _query: &str, _documents: &[String]) -> Result<Vec<RerankScore>> {
+            anyhow::bail!("connection refused")
+        }
+    }
+
+    #[test]
+    fn embedding_check_reports_latency_for_matching_dimension() -> Result<()> {
+        let check = ProviderCheck::from_result(
+            "embedding",
+            "openai.embed",
+            check_embedding(&FixedEmbeddingProvider {
+                dimension: 4,
+                returned: 4,
+            }),
+        );
+
+        assert!(check.ok);
+        assert!(check.latency_ms.is_some());
+        assert!(check.detail.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn embedding_check_fails_on_dimension_mismatch() {
+        let error = check_embedding(&FixedEmbeddingProvider {
+            dimension: 1536,
+            returned: 1024,
+        })
+        .expect_err("expected dimension mismatch");
+
+        assert!(error.to_string().contains("configured 1536"));
+    }
+
+    #[test]
+    fn failed_check_keeps_error_chain_as_detail() {
+        let check = ProviderCheck::from_result(
+            "rerank",
+            "aliyun.rerank",
+            check_rerank(&UnavailableRerankProvider),
+        );
+
+        assert!(!check.ok);
+        assert!(check.latency_ms.is_none());
+        let detail = check.detail.expect("expected failure detail");
+        assert!(detail.contains("rerank probe failed"));
+        assert!(detail.contains("connection refused"));
+    }
+}
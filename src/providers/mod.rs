@@ -1,3 +1,4 @@
 pub(crate) mod adapters;
+pub(crate) mod health;
 pub(crate) mod runtime;
 pub(crate) mod status;